@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, ParseError};
+use crate::solution::Answer;
+
+// Known answers from `answers.toml`, keyed by day, part and input name:
+//
+//     [d17.part_1]
+//     sample = "4,6,3,5,6,3,5,2,1,0"
+//     real = "7,6,1,5,3,1,4,2,6"
+//
+// Answers are kept as the text they display as, so integer and string answers compare the same
+// way as `Answer`'s `Display`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32, String), String>,
+}
+
+// `$AOC_ANSWERS`, or `answers.toml` in the working directory
+pub fn path() -> PathBuf {
+    env::var_os("AOC_ANSWERS").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("answers.toml"))
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Error::Io { path: path.to_path_buf(), message: e.to_string() })?;
+        Self::parse(&text).map_err(|message| Error::Answers { path: path.to_path_buf(), message })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in &table {
+            let day = number(day_key, "d")?;
+            let parts = parts.as_table().ok_or(format!("[{}] should be a table of parts", day_key))?;
+            for (part_key, inputs) in parts {
+                let part = number(part_key, "part_")?;
+                let inputs = inputs.as_table().ok_or(format!("[{}.{}] should be a table of inputs", day_key, part_key))?;
+                for (name, value) in inputs {
+                    let answer = match value {
+                        toml::Value::Integer(n) => n.to_string(),
+                        toml::Value::String(s) => s.clone(),
+                        other => return Err(format!("{}.{}.{}: expected an integer or string, got {}", day_key, part_key, name, other)),
+                    };
+                    answers.insert((day, part, name.clone()), answer);
+                }
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&str> {
+        self.answers.get(&(day, part, input.to_string())).map(|s| s.as_str())
+    }
+
+    // (input name, answer) for every recorded input of one day and part
+    pub fn for_part(&self, day: u32, part: u32) -> Vec<(&str, &str)> {
+        self.answers
+            .iter()
+            .filter(|((d, p, _), _)| *d == day && *p == part)
+            .map(|((_, _, name), answer)| (name.as_str(), answer.as_str()))
+            .collect()
+    }
+}
+
+// Check one part of a day against the repo's `answers.toml`, for the day's tests. Parts without a
+// recorded answer are skipped without reading the input, so a new day's tests pass until it's
+// solved.
+#[cfg(test)]
+pub fn check<T: Into<Answer>>(
+    day: u32,
+    part: u32,
+    name: &str,
+    input: impl FnOnce() -> String,
+    solve: impl FnOnce(&str) -> Result<T, Error>,
+) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+    let answers = Answers::load(&path).unwrap();
+    let Some(expected) = answers.get(day, part, name) else {
+        println!("no answer for d{:02}.part_{}.{} in answers.toml", day, part, name);
+        return;
+    };
+    let answer: Answer = solve(&input()).unwrap().into();
+    assert_eq!(answer.to_string(), expected, "d{:02}.part_{}.{}", day, part, name);
+}
+
+// the number in a key like `d07` or `part_2`
+fn number(key: &str, prefix: &str) -> Result<u32, String> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or(format!("unexpected key {:?}, expected {}<number>", key, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("[d17.part_1]\nsample = \"4,6,3\"\nreal = 12\n\n[d17.part_2]\nreal = 5\n").unwrap();
+        assert_eq!(answers.get(17, 1, "sample"), Some("4,6,3"));
+        assert_eq!(answers.get(17, 1, "real"), Some("12"));
+        assert_eq!(answers.get(17, 2, "sample"), None);
+        assert_eq!(answers.for_part(17, 1), vec![("real", "12"), ("sample", "4,6,3")]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Answers::parse("[day1.part_1]\nreal = 1").unwrap_err(), "unexpected key \"day1\", expected d<number>");
+        assert_eq!(Answers::parse("[d01.part_1]\nreal = 1.5").unwrap_err(), "d01.part_1.real: expected an integer or string, got 1.5");
+        assert!(Answers::parse("[d01.part_1\n").is_err());
+    }
+
+    #[test]
+    fn check_recorded() {
+        check(3, 2, "sample2", || crate::days::d03::inputs::SAMPLE2.into(), |input| crate::days::d03::part_2(&crate::days::d03::parse(input)?));
+        // nothing is recorded for day 0, so the input isn't even read
+        check(0, 1, "real", || unreachable!(), |_| Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "d03.part_2.sample2")]
+    fn check_wrong() {
+        check(3, 2, "sample2", || crate::days::d03::inputs::SAMPLE2.into(), |_| Ok(49));
+    }
+
+    #[test]
+    fn repo_answers() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.get(1, 1, "real"), Some("1580061"));
+        assert_eq!(answers.get(24, 2, "real"), Some("-"));
+    }
+}
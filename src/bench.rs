@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::runner;
+
+// Benchmarks in the style of criterion: warm up, take a number of samples that fit in a time
+// budget, and compare the median against a saved baseline.
+
+// most samples to take of one benchmark
+const SAMPLES: u32 = 20;
+
+// a change in the median smaller than this is noise
+const NOISE: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+// Time `f` for about `budget`. The first call is a warm-up, and catches panics like the runner
+// does. If it takes longer than the budget, it's the only sample.
+pub fn measure<T>(mut f: impl FnMut() -> Result<T, Error>, budget: Duration) -> Result<Stats, Error> {
+    let (_, first) = runner::timed(&mut f)?;
+    let first = first.max(Duration::from_nanos(1));
+
+    let samples = (budget.as_nanos() / first.as_nanos()).clamp(1, SAMPLES as u128) as u32;
+    // fast benchmarks run many iterations per sample, so the clock's resolution doesn't matter
+    let iters = (budget.as_nanos() / samples as u128 / first.as_nanos()).max(1) as u32;
+
+    let mut times: Vec<Duration> = Vec::new();
+    if samples == 1 && iters == 1 {
+        times.push(first);
+    } else {
+        for _ in 0..samples {
+            let start = Instant::now();
+            for _ in 0..iters {
+                black_box(f()?);
+            }
+            times.push(start.elapsed() / iters);
+        }
+    }
+    times.sort();
+    Ok(Stats { min: times[0], median: times[times.len() / 2], max: times[times.len() - 1] })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    // within the noise threshold
+    None(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+// how `new` compares to `old`, as a fraction of `old`
+pub fn compare(old: Duration, new: Duration) -> Change {
+    let change = (new.as_secs_f64() - old.as_secs_f64()) / old.as_secs_f64();
+    if change > NOISE {
+        Change::Regressed(change)
+    } else if change < -NOISE {
+        Change::Improved(change)
+    } else {
+        Change::None(change)
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Change::None(c) => write!(f, "{:+.2}% (no change)", c * 100.0),
+            Change::Improved(c) => write!(f, "{:+.2}% (improved)", c * 100.0),
+            Change::Regressed(c) => write!(f, "{:+.2}% (regressed)", c * 100.0),
+        }
+    }
+}
+
+// Median times from an earlier run, keyed by day and benchmark (`parse`, `part_1`, `part_2`).
+// Saved as toml, like answers.toml:
+//
+//     [d01]
+//     parse = 45100
+//     part_1 = 51870
+//
+// with times in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    times: BTreeMap<(u32, String), Duration>,
+}
+
+// `target/aoc-bench/<name>.toml` in the crate
+pub fn baseline_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("aoc-bench").join(format!("{}.toml", name))
+}
+
+impl Baseline {
+    // an empty baseline if there's no file yet
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| Error::Io { path: path.to_path_buf(), message: e.to_string() })?;
+        Self::parse(&text).map_err(|message| Error::Io { path: path.to_path_buf(), message })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let mut times = BTreeMap::new();
+        for (day_key, benches) in &table {
+            let day = day_key
+                .strip_prefix('d')
+                .and_then(|n| n.parse().ok())
+                .ok_or(format!("unexpected key {:?}, expected d<number>", day_key))?;
+            let benches = benches.as_table().ok_or(format!("[{}] should be a table of times", day_key))?;
+            for (name, nanos) in benches {
+                let nanos = nanos
+                    .as_integer()
+                    .and_then(|n| u64::try_from(n).ok())
+                    .ok_or(format!("{}.{}: expected a time in nanoseconds", day_key, name))?;
+                times.insert((day, name.clone()), Duration::from_nanos(nanos));
+            }
+        }
+        Ok(Self { times })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut table = toml::Table::new();
+        for ((day, name), time) in &self.times {
+            let day = table
+                .entry(format!("d{:02}", day))
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(day) = day {
+                day.insert(name.clone(), toml::Value::Integer(time.as_nanos() as i64));
+            }
+        }
+        let io_error = |e: std::io::Error| Error::Io { path: path.to_path_buf(), message: e.to_string() };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(path, table.to_string()).map_err(io_error)
+    }
+
+    pub fn get(&self, day: u32, name: &str) -> Option<Duration> {
+        self.times.get(&(day, name.to_string())).copied()
+    }
+
+    pub fn set(&mut self, day: u32, name: &str, time: Duration) {
+        self.times.insert((day, name.to_string()), time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_samples() {
+        let mut calls = 0;
+        let stats = measure(|| { calls += 1; Ok(()) }, Duration::from_millis(10)).unwrap();
+        assert!(calls > SAMPLES);
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+
+        // slower than the budget, so it only runs once
+        let mut calls = 0;
+        measure(|| { calls += 1; std::thread::sleep(Duration::from_millis(2)); Ok(()) }, Duration::from_millis(1)).unwrap();
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn measure_errors() {
+        assert_eq!(measure(|| Err::<(), _>(Error::UnknownDay(0)), Duration::ZERO), Err(Error::UnknownDay(0)));
+        assert!(matches!(measure(|| -> Result<(), Error> { panic!("oops") }, Duration::ZERO), Err(Error::Panic(_))));
+    }
+
+    #[test]
+    fn changes() {
+        let ms = Duration::from_millis;
+        assert!(matches!(compare(ms(100), ms(102)), Change::None(_)));
+        assert!(matches!(compare(ms(100), ms(150)), Change::Regressed(_)));
+        assert_eq!(compare(ms(100), ms(50)).to_string(), "-50.00% (improved)");
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.set(1, "parse", Duration::from_nanos(45100));
+        baseline.set(18, "part_2", Duration::from_secs(86));
+        let dir = crate::helpers::temp_dir("baseline");
+        let path = dir.join("baseline.toml");
+        baseline.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[d01]\nparse = 45100\n\n[d18]\npart_2 = 86000000000\n");
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Baseline::load(Path::new("no/such/baseline.toml")).unwrap(), Baseline::default());
+        assert!(Baseline::parse("[d01]\nparse = \"fast\"").is_err());
+    }
+}
@@ -1,25 +1,81 @@
-pub mod d01;
-pub mod d02;
-pub mod d03;
-pub mod d04;
-pub mod d05;
-pub mod d06;
-pub mod d07;
-pub mod d08;
-pub mod d09;
-pub mod d10;
-pub mod d11;
-pub mod d12;
-pub mod d13;
-pub mod d14;
-pub mod d15;
-pub mod d16;
-pub mod d17;
-pub mod d18;
-pub mod d19;
-pub mod d20;
-pub mod d21;
-pub mod d22;
-pub mod d23;
-pub mod d24;
-pub mod d25;
+use std::any::Any;
+
+use crate::error::ParseError;
+use crate::solution::{Config, Day, Parsed, Solution};
+
+// Wraps a day's `part_1`/`part_2` to fit `Solution`, handing them the day's parsed input back as
+// the type its `parse` returns. Days marked `(params)` also take their `Params`, built from the
+// config.
+macro_rules! part {
+    ($d:ident::$f:ident) => {
+        |parsed: &dyn Any, config: &Config| {
+            config.params::<()>()?;
+            let parsed = parsed_as(parsed, $d::parse);
+            Ok($d::$f(parsed)?.into())
+        }
+    };
+    ($d:ident::$f:ident, params) => {
+        |parsed: &dyn Any, config: &Config| {
+            let parsed = parsed_as(parsed, $d::parse);
+            Ok($d::$f(parsed, &config.params::<$d::Params>()?)?.into())
+        }
+    };
+}
+
+// `parsed` as what `parse` returns, which it always is since it came from there
+fn parsed_as<T: 'static>(parsed: &dyn Any, _parse: fn(&str) -> Result<T, ParseError>) -> &T {
+    parsed.downcast_ref().expect("parsed input from another day")
+}
+
+// Declares every day module and registers its solution, so adding a day is one line below.
+macro_rules! days {
+    ($($n:literal => $d:ident $(($p:ident))?),* $(,)?) => {
+        $( pub mod $d; )*
+
+        pub static REGISTRY: &[Day] = &[
+            $( Day::new(
+                $n,
+                $d::inputs::ALL,
+                |input| Ok(Box::new($d::parse(input)?) as Parsed),
+                part!($d::part_1 $(, $p)?),
+                part!($d::part_2 $(, $p)?),
+            ), )*
+        ];
+    };
+}
+
+days! {
+    1 => d01,
+    2 => d02,
+    3 => d03,
+    4 => d04,
+    5 => d05,
+    6 => d06,
+    7 => d07,
+    8 => d08,
+    9 => d09,
+    10 => d10,
+    11 => d11,
+    12 => d12,
+    13 => d13,
+    14 => d14(params),
+    15 => d15,
+    16 => d16,
+    17 => d17,
+    18 => d18(params),
+    19 => d19,
+    20 => d20(params),
+    21 => d21,
+    22 => d22,
+    23 => d23(params),
+    24 => d24,
+    25 => d25,
+}
+
+// the skeleton `new-day` copies, built with the tests so it always compiles
+#[cfg(test)]
+pub mod template;
+
+pub fn get(day: u32) -> Option<&'static dyn Solution> {
+    REGISTRY.iter().find(|d| d.day() == day).map(|d| d as &dyn Solution)
+}
@@ -1,44 +1,50 @@
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 1;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(1) }
 }
 
-fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
-    let lines = crate::helpers::to_lines(input);
+pub fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut ids1: Vec<i32> = Vec::new();
     let mut ids2: Vec<i32> = Vec::new();
 
-    for line in lines {
-        println!("{}", line);
-        let items: Vec<&str> = line.split("   ").collect();
-        ids1.push(items[0].parse::<i32>().unwrap_or(0));
-        ids2.push(items[1].parse::<i32>().unwrap_or(0));
+    for line in crate::helpers::lines(DAY, input) {
+        let (a, b) = line.split_once("   ")?;
+        ids1.push(line.num(a)?);
+        ids2.push(line.num(b)?);
     }
 
-    (ids1, ids2)
+    Ok((ids1, ids2))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (mut ids1, mut ids2) = parse(input);
+pub fn part_1(parsed: &(Vec<i32>, Vec<i32>)) -> Result<i32, Error> {
+    let (mut ids1, mut ids2) = parsed.clone();
 
     ids1.sort();
     ids2.sort();
 
-    ids1.iter()
+    Ok(ids1.iter()
         .zip(ids2.iter())
         .map(|(a, b)| (a - b).abs())
-        .sum()
+        .sum())
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let (ids1, ids2) = parse(input);
+pub fn part_2(parsed: &(Vec<i32>, Vec<i32>)) -> Result<i32, Error> {
+    let (ids1, ids2) = parsed;
     
-    ids1.iter()
+    Ok(ids1.iter()
         .map(|id| {
             let count = ids2.iter().filter(|&x| *x == *id).count() as i32;
             id * count
         })
-        .sum()
+        .sum())
 }
 
 #[cfg(test)]
@@ -47,21 +53,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 11);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(11));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 1580061);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(1580061));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 31);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(31));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 23046913);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(23046913));
     }
 }
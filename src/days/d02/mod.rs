@@ -1,31 +1,34 @@
 use itertools::Itertools;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 2;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(2) }
 }
 
-fn parse(input: &str) -> Vec<Vec<i32>> {
-    let lines = crate::helpers::to_lines(input);
-    lines.iter().map(|l| {
-        l.split(" ").map(|n| n.parse::<i32>().unwrap_or(0)).collect()
-    }).collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    crate::helpers::lines(DAY, input).map(|l| l.nums(" ")).collect()
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let reports = parse(input);
+pub fn part_1(reports: &[Vec<i32>]) -> Result<i32, Error> {
     let mut safe = 0;
     for report in reports {
-        if report_safe(&report) { safe += 1 }
+        if report_safe(report) { safe += 1 }
     }
-    safe
+    Ok(safe)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let reports = parse(input);
+pub fn part_2(reports: &[Vec<i32>]) -> Result<i32, Error> {
     let mut safe = 0;
     for report in reports {
-        if report_safe(&report) {
+        if report_safe(report) {
             safe += 1;
         } else {
             for (i, n) in report.iter().enumerate() {
@@ -38,17 +41,17 @@ pub fn part_2(input: &str) -> i32 {
             }
         }
     }
-    safe
+    Ok(safe)
 }
 
-fn report_safe(report: &Vec<i32>) -> bool {
+fn report_safe(report: &[i32]) -> bool {
     let diffs: Vec<i32> = report.iter().tuple_windows().map(|(a,b)| a - b).collect();
     let diff_pos = diffs.iter().all(|n| *n > 0);
     let diff_neg = diffs.iter().all(|n| *n < 0);
     if diff_pos || diff_neg {
         return diffs.iter().all(|n| {
             let abs = n.abs();
-            abs >= 1 && abs <= 3
+            (1..=3).contains(&abs)
         });
     }
     false
@@ -60,21 +63,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 2);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(2));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 463);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(463));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 4);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(4));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 514);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(514));
     }
 }
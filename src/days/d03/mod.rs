@@ -1,39 +1,43 @@
 use regex::Regex;
 
+use crate::error::{Error, ParseError};
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE), ("sample2", SAMPLE2)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(3) }
 }
 
-fn parse(input: &str) -> String {
-    input.to_string()
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    Ok(input.to_string())
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mem = parse(input);
+pub fn part_1(mem: &str) -> Result<i32, Error> {
 
     let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
     let mut sum = 0;
 
-    for (_full, [a, b]) in re.captures_iter(&mem).map(|c| c.extract()) {
+    for (_full, [a, b]) in re.captures_iter(mem).map(|c| c.extract()) {
         let a = a.parse::<i32>().unwrap();
         let b = b.parse::<i32>().unwrap();
         sum += a * b
     }
 
-    sum
+    Ok(sum)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let mem = parse(input);
+pub fn part_2(mem: &str) -> Result<i32, Error> {
 
     let re = Regex::new(r"do\(\)|don't\(\)|mul\((\d+),(\d+)\)").unwrap();
 
     let mut on = true;
     let mut sum = 0;
 
-    for cap in re.captures_iter(&mem) {
+    for cap in re.captures_iter(mem) {
         let full = cap.get(0).unwrap().as_str();
         if (full == "do()") {
             on = true;
@@ -48,7 +52,7 @@ pub fn part_2(input: &str) -> i32 {
         }
     }
 
-    sum
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -57,21 +61,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 161);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(161));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 165225049);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(165225049));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE2), 48);
+        assert_eq!(part_2(&parse(inputs::SAMPLE2).unwrap()), Ok(48));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 108830766);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(108830766));
     }
 }
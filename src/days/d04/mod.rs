@@ -1,46 +1,47 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::{Dir8, Grid, Point};
+
+const DAY: u32 = 4;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(4) }
 }
 
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_chars(DAY, input, "'X', 'M', 'A' or 'S'", |c| "XMAS".contains(c))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let grid: Grid<char> = parse(input);
+pub fn part_1(grid: &Grid<char>) -> Result<i32, Error> {
 
     let mut xmas = 0;
 
     for (c, x, y) in grid.iter() {
-        if c != Some('X') { continue; }
-        for (c, dx, dy) in grid.neighbors(x, y, true).iter() {
-            if (*c != Some('M')) { continue; }
-            if (grid.get(x + dx * 2, y + dy * 2) != Some('A')) { continue; }
-            if (grid.get(x + dx * 3, y + dy * 3) != Some('S')) { continue; }
-            xmas += 1;
+        if *c != 'X' { continue; }
+        for dir in Dir8::ALL {
+            if grid.ray(Point::new(x, y), dir.offset()).take(3).map(|(_, c)| *c).eq("MAS".chars()) {
+                xmas += 1;
+            }
         }
     }
 
-    xmas
+    Ok(xmas)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let grid: Grid<char> = parse(input);
+pub fn part_2(grid: &Grid<char>) -> Result<i32, Error> {
 
     let mut x_mas = 0;
 
-    for (c, x, y) in grid.iter() {
-        if c != Some('A') { continue; }
+    for (_, window) in grid.windows(3, 3) {
+        if window[(1, 1)] != 'A' { continue; }
 
-        let tl = grid.get(x-1, y-1);
-        let tr = grid.get(x+1, y-1);
-        let bl = grid.get(x-1, y+1);
-        let br = grid.get(x+1, y+1);
+        let [tl, tr, bl, br] = [(0, 0), (2, 0), (0, 2), (2, 2)].map(|p| window[p]);
 
-        if (![tl, tr, bl, br].iter().all(|c| *c == Some('M') || *c == Some('S'))) {
+        if (![tl, tr, bl, br].iter().all(|c| *c == 'M' || *c == 'S')) {
             continue;
         }
 
@@ -49,7 +50,7 @@ pub fn part_2(input: &str) -> i32 {
         }
     }
 
-    x_mas
+    Ok(x_mas)
 }
 
 #[cfg(test)]
@@ -58,21 +59,27 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 18);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(18));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 2618);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(2618));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 9);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(9));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 2011);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(2011));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("XMAS\nXM-S").unwrap_err();
+        assert_eq!(err.to_string(), "day 4, line 2: column 3: unexpected '-' (expected 'X', 'M', 'A' or 'S'): \"XM-S\"");
     }
 }
@@ -1,52 +1,60 @@
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 5;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(5) }
 }
 
-fn parse(input: &str) -> (Vec<(i32, i32)>, Vec<Vec<i32>>) {
-    let lines = crate::helpers::to_lines(input);
-    let mut rules: Vec<(i32, i32)> = Vec::new();
+// each rule is a pair of pages, the first of which has to come before the second
+pub type Rules = Vec<(i32, i32)>;
+
+pub fn parse(input: &str) -> Result<(Rules, Vec<Vec<i32>>), ParseError> {
+    let mut rules: Rules = Vec::new();
     let mut updates: Vec<Vec<i32>> = Vec::new();
 
-    for line in lines {
-        if (line.contains("|")) {
-            let nums: Vec<i32> = line.split("|").map(|n| n.parse::<i32>().unwrap_or(0)).collect();
-            rules.push((nums[0], nums[1]));
-        } else if (line.contains(",")) {
-            updates.push(
-                line.split(",").map(|n| n.parse::<i32>().unwrap_or(0)).collect()
-            );
+    for line in crate::helpers::lines(DAY, input) {
+        if (line.text.contains("|")) {
+            let (a, b) = line.split_once("|")?;
+            rules.push((line.num(a)?, line.num(b)?));
+        } else if (!line.text.is_empty()) {
+            updates.push(line.nums(",")?);
         }
     }
 
-    (rules, updates)
+    Ok((rules, updates))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (rules, updates) = parse(input);
+pub fn part_1(parsed: &(Rules, Vec<Vec<i32>>)) -> Result<i32, Error> {
+    let (rules, updates) = parsed;
     let mut sum = 0;
     for update in updates {
-        if is_good(&rules, &update) {
-            sum += get_middle(&update);
+        if is_good(rules, update) {
+            sum += get_middle(update);
         }
     }
-    sum
+    Ok(sum)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let (rules, updates) = parse(input);
+pub fn part_2(parsed: &(Rules, Vec<Vec<i32>>)) -> Result<i32, Error> {
+    let (rules, updates) = parsed;
 
     let mut sum = 0;
     for update in updates {
-        if !is_good(&rules, &update) {
-            let update = sort_by_rules(&rules, update);
+        if !is_good(rules, update) {
+            let update = sort_by_rules(rules, update.clone());
             sum += get_middle(&update);
         }
     }
-    sum
+    Ok(sum)
 }
 
-fn is_good(rules: &Vec<(i32, i32)>, update: &Vec<i32>) -> bool {
+fn is_good(rules: &[(i32, i32)], update: &[i32]) -> bool {
     let mut good = true;
     for (i, num) in update.iter().enumerate() {
         for other in &update[(i+1)..] {
@@ -58,20 +66,20 @@ fn is_good(rules: &Vec<(i32, i32)>, update: &Vec<i32>) -> bool {
     good
 }
 
-fn get_middle(update: &Vec<i32>) -> i32 {
+fn get_middle(update: &[i32]) -> i32 {
     let mid_i = (update.len() - 1) / 2;
     update[mid_i]
 }
 
-fn sort_by_rules(rules: &Vec<(i32, i32)>, mut update: Vec<i32>) -> Vec<i32> {
+fn sort_by_rules(rules: &[(i32, i32)], mut update: Vec<i32>) -> Vec<i32> {
     update.sort_by(|a, b| {
         // there is a rule for every pair of numbers, so find the one for these two
         let rule = rules.iter().find(|r| (r.0 == *a && r.1 == *b) || (r.0 == *b && r.1 == *a) ).unwrap();
         // if a is first, then sort a < b, else reverse
         if rule.0 == *a {
-            return 0.cmp(&1);
+            0.cmp(&1)
         } else {
-            return 1.cmp(&0);
+            1.cmp(&0)
         }
     });
     update
@@ -83,21 +91,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 143);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(143));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 5762);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(5762));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 123);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(123));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 4130);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(4130));
     }
 }
@@ -1,105 +1,94 @@
 use std::{thread, time};
 
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::{Dir4, Grid, Point};
+
+const DAY: u32 = 6;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
-}
 
-#[derive(Copy,Clone,Debug,PartialEq)]
-pub enum Dir { U, D, L, R }
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(6) }
+}
 
 pub enum Result { Exit, Loop }
 
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
+pub fn parse(input: &str) -> std::result::Result<Grid<char>, ParseError> {
+    let grid = Grid::parse_chars(DAY, input, "'.', '#' or '^'", |c| ".#^".contains(c))?;
+    crate::helpers::one_marker(DAY, input, '^', "guard '^'")?;
+    Ok(grid)
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mut grid: Grid<char> = parse(input);
-    let (res, trail) = walk(&mut grid, Dir::U);
-    let mut cells: Vec<(i32, i32)> = Vec::new();
+pub fn part_1(grid: &Grid<char>) -> std::result::Result<i32, Error> {
+    let mut grid = grid.clone();
+    let (res, trail) = walk(&mut grid, Dir4::U);
+    let mut cells: Vec<Point> = Vec::new();
     for (cell, dir) in trail {
         if !cells.contains(&cell) { cells.push(cell) }
     }
     // plus last position before exit
-    (cells.len() + 1).try_into().unwrap()
+    Ok((cells.len() + 1).try_into().unwrap())
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let grid: Grid<char> = parse(input);
-    let mut grid: Grid<char> = parse(input);
+pub fn part_2(grid: &Grid<char>) -> std::result::Result<i32, Error> {
+    let mut grid = grid.clone();
     let mut obstacles = grid.clone();
 
     // walk the grid once to get the trail
-    let (res, trail) = walk(&mut grid.clone(), Dir::U);
+    let (res, trail) = walk(&mut grid.clone(), Dir4::U);
     // for each step in the trail, place an obstacle in the next pos and walk.
     // if we loop, mark it in the `obstacles` grid clone
     for (i, (pos, dir)) in trail.iter().enumerate() {
         let mut grid2 = grid.clone();
-        let next = next_pos(*pos, *dir);
-        match grid2.get(next.0, next.1) {
-            Some('.') | Some('X') => grid2.set('O', next.0, next.1),
-            Some('^') | Some('#') | None => continue,
-            Some(other) => panic!("uenxpected char in grid {}", other)
+        let next = pos.step(*dir);
+        match grid2.get_at(next) {
+            Some('.') | Some('X') => grid2.set_at('O', next),
+            _ => continue,
         }
 
-        match walk(&mut grid2, Dir::U) {
+        match walk(&mut grid2, Dir4::U) {
             (Result::Exit, _) => (),
-            (Result::Loop, _) => { obstacles.set('O', next.0, next.1) }
+            (Result::Loop, _) => { obstacles.set_at('O', next) }
         }
     }
 
     //obstacles.draw();
-    obstacles.count('O')
+    Ok(obstacles.count('O'))
 }
 
 // wrapper around recursive _walk fn that initializes and returns the trail
-pub fn walk(grid: &mut Grid<char>, dir: Dir) -> (Result, Vec<((i32, i32), Dir)>) {
-    let mut trail: Vec<((i32, i32), Dir)> = Vec::new();
+pub fn walk(grid: &mut Grid<char>, dir: Dir4) -> (Result, Vec<(Point, Dir4)>) {
+    let mut trail: Vec<(Point, Dir4)> = Vec::new();
     let res = _walk(grid, dir, &mut trail);
     (res, trail)
 }
 
-fn next_pos(pos: (i32, i32), dir: Dir) -> (i32, i32) {
-    let offset = match dir {
-        Dir::U => (0, -1),
-        Dir::R => (1, 0),
-        Dir::D => (0, 1),
-        Dir::L => (-1, 0)
-    };
-    (pos.0 + offset.0, pos.1 + offset.1)
-}
-
-fn next_dir(dir: Dir) -> Dir {
-    match dir {
-        Dir::U => Dir::R,
-        Dir::R => Dir::D,
-        Dir::D => Dir::L,
-        Dir::L => Dir::U
-    }
-}
-
-// recursive fn to walk the grid
-pub fn _walk(grid: &mut Grid<char>, dir: Dir, trail: &mut Vec<((i32, i32), Dir)>) -> Result {
+// recursive fn to walk the grid, straight ahead until something's in the way and then turning
+pub fn _walk(grid: &mut Grid<char>, dir: Dir4, trail: &mut Vec<(Point, Dir4)>) -> Result {
     //thread::sleep(time::Duration::from_millis(100));
     //grid.draw();
-    let pos = grid.find('^').unwrap();
-    let next = next_pos(pos, dir);
-    match grid.get(next.0, next.1) {
-        Some('.') | Some('X') => {
-            let step = (pos, dir);
-            if (trail.contains(&step)) { return Result::Loop }
-            trail.push(step);
-            //grid.set('X', pos.0, pos.1);
-            grid.set('.', pos.0, pos.1);
-            grid.set('^', next.0, next.1);
-            return _walk(grid, dir, trail);
-        },
-        Some('#') | Some('O') => return _walk(grid, next_dir(dir), trail),
-        None => return Result::Exit,
-        Some(other) => panic!("unxpected char in grid {}", other)
+    // `parse` checked there's a guard, who is moved along with each step
+    let Some(mut pos) = grid.find_point('^') else { return Result::Exit };
+    let ahead: Vec<Point> = grid.ray(pos, dir.offset())
+        .take_while(|(_, c)| matches!(c, '.' | 'X'))
+        .map(|(p, _)| p)
+        .collect();
+    for next in ahead {
+        let step = (pos, dir);
+        if (trail.contains(&step)) { return Result::Loop }
+        trail.push(step);
+        //grid.set_at('X', pos);
+        grid.set_at('.', pos);
+        grid.set_at('^', next);
+        pos = next;
+    }
+    match grid.get_at(pos.step(dir)) {
+        None => Result::Exit,
+        // anything that stopped the ray is in the way, i.e. '#' or 'O'
+        Some(_) => _walk(grid, dir.turn_right(), trail),
     }
 }
 
@@ -109,22 +98,31 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 41);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(41));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 4778);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(4778));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 6);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(6));
     }
 
     #[test]
+    #[ignore = "slow; run with --release --ignored"]
     fn part_2_real() {
         // this takes a couple minutes on release build
-        //assert_eq!(part_2(inputs::REAL), 1618);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(1618));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("..#\n.x^").unwrap_err();
+        assert_eq!(err.to_string(), "day 6, line 2: column 2: unexpected 'x' (expected '.', '#' or '^'): \".x^\"");
+        assert_eq!(parse("..#\n...").unwrap_err().to_string(), "day 6: no guard '^'");
+        assert_eq!(parse("..^\n^..").unwrap_err(), ParseError::new(DAY, 2, "^..", "more than one guard '^'"));
     }
 }
@@ -1,39 +1,40 @@
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 7;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(7) }
 }
 
-fn parse(input: &str) -> Vec<(i64, Vec<i64>)> {
-    crate::helpers::to_lines(input)
-        .iter()
+pub fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
+    crate::helpers::lines(DAY, input)
         .map(|l| {
-            let split: Vec<&str> = l.split(": ").collect();
-            let test = split[0];
-            let nums: Vec<&str> = split[1].split(" ").collect();
-            (
-                test.parse::<i64>().unwrap(),
-                nums.iter().map(|n| n.parse::<i64>().unwrap()).collect()
-            )
+            let (test, nums) = l.split_once(": ")?;
+            let nums = nums.split(" ").map(|n| l.num(n)).collect::<Result<Vec<i64>, _>>()?;
+            Ok((l.num(test)?, nums))
         })
         .collect()
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let eqs = parse(input);
+pub fn part_1(eqs: &[(i64, Vec<i64>)]) -> Result<i64, Error> {
     let mut cal_res = 0;
     for eq in eqs {
         if can_eval_true(eq.clone(), false) { cal_res += eq.0 }
     }
-    cal_res
+    Ok(cal_res)
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let eqs = parse(input);
+pub fn part_2(eqs: &[(i64, Vec<i64>)]) -> Result<i64, Error> {
     let mut cal_res = 0;
     for eq in eqs {
         if can_eval_true(eq.clone(), true) { cal_res += eq.0 }
     }
-    cal_res
+    Ok(cal_res)
 }
 
 enum Op { Add, Mul, Join }
@@ -42,14 +43,12 @@ fn can_eval_true(eq: (i64, Vec<i64>), do_join: bool) -> bool {
     let (test, nums) = eq;
     if can_eval_true_op(test, nums.clone(), Op::Add, do_join) { return true; }
     if can_eval_true_op(test, nums.clone(), Op::Mul, do_join) { return true; }
-    if do_join {
-        if can_eval_true_op(test, nums.clone(), Op::Join, do_join) { return true; }
-    }
+    if do_join && can_eval_true_op(test, nums.clone(), Op::Join, do_join) { return true; }
     false
 }
 
 fn can_eval_true_op(test: i64, mut nums: Vec<i64>, op: Op, do_join: bool) -> bool {
-    if nums.len() == 0 { panic!("nums is empty!") }
+    if nums.is_empty() { panic!("nums is empty!") }
     if nums.len() == 1 { return test == nums[0] }
 
     let a = nums.remove(0);
@@ -67,9 +66,7 @@ fn can_eval_true_op(test: i64, mut nums: Vec<i64>, op: Op, do_join: bool) -> boo
     };
     if can_eval_true_op(test, nums.clone(), Op::Add, do_join) { return true; }
     if can_eval_true_op(test, nums.clone(), Op::Mul, do_join) { return true; }
-    if do_join {
-        if can_eval_true_op(test, nums.clone(), Op::Join, do_join) { return true; }
-    }
+    if do_join && can_eval_true_op(test, nums.clone(), Op::Join, do_join) { return true; }
     false
 }
 
@@ -79,22 +76,23 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 3749);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(3749));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 5030892084481);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(5030892084481));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 11387);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(11387));
     }
 
     #[test]
+    #[ignore = "slow; run with --release --ignored"]
     fn part_2_real() {
         // takes 5-10 secs
-        //assert_eq!(part_2(inputs::REAL), 91377448644679);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(91377448644679));
     }
 }
@@ -1,56 +1,57 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::{Grid, Point, SparseGrid};
+
+const DAY: u32 = 8;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(8) }
+}
+
+// antennas are letters and digits, with '.' between them
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_chars(DAY, input, "'.' or an antenna", |c| c == '.' || c.is_ascii_alphanumeric())
 }
 
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
+// just the antennas, so pairing them up doesn't visit every empty cell
+fn antennas(grid: &Grid<char>) -> SparseGrid<char> {
+    SparseGrid::from_grid(grid, |c| *c != '.')
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let grid = parse(input);
-    let mut antinodes = Grid::fresh('.', grid.get_width(), grid.get_height());
-    for (c, x, y) in grid.iter() {
-        if (c == Some('.')) { continue }
-        for (c2, x2, y2) in grid.iter() {
+pub fn part_1(grid: &Grid<char>) -> Result<i32, Error> {
+    let antennas = antennas(grid);
+    let mut antinodes = SparseGrid::new('.');
+    for (p, c) in antennas.iter() {
+        for (p2, c2) in antennas.iter() {
             if (c != c2) { continue }
-            if (x == x2 && y == y2) { continue }
-            let ox = x2 - x;
-            let oy = y2 - y;
-            let ax = x - ox;
-            let ay = y - oy;
-            if (ax >= 0 && ax <= (antinodes.get_width() - 1) && ay >= 0 && ay <= (antinodes.get_height() - 1)) {
-                antinodes.set('#', ax, ay);
+            if (p == p2) { continue }
+            if let Some((a, _)) = grid.ray(p, p - p2).next() {
+                antinodes.set('#', a);
             }
         }
     }
-    antinodes.count('#')
+    Ok(antinodes.len() as i32)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let grid = parse(input);
-    let mut antinodes = Grid::fresh('.', grid.get_width(), grid.get_height());
-    for (c, x, y) in grid.iter() {
-        if (c == Some('.')) { continue }
-        for (c2, x2, y2) in grid.iter() {
+pub fn part_2(grid: &Grid<char>) -> Result<i32, Error> {
+    let antennas = antennas(grid);
+    let mut antinodes = SparseGrid::new('.');
+    for (p, c) in antennas.iter() {
+        for (p2, c2) in antennas.iter() {
             if (c != c2) { continue }
-            if (x == x2 && y == y2) { continue }
-            antinodes.set('#', x, y);
-            antinodes.set('#', x2, y2);
-            let mut ox = x2 - x;
-            let mut oy = y2 - y;
-            let mut ax = x - ox;
-            let mut ay = y - oy;
-            while (ax >= 0 && ax <= (antinodes.get_width() - 1) && ay >= 0 && ay <= (antinodes.get_height() - 1)) {
-                antinodes.set('#', ax, ay);
-                ax = ax - ox;
-                ay = ay - oy;
+            if (p == p2) { continue }
+            antinodes.set('#', p);
+            antinodes.set('#', p2);
+            for (a, _) in grid.ray(p, p - p2) {
+                antinodes.set('#', a);
             }
         }
     }
-    antinodes.count('#')
+    Ok(antinodes.len() as i32)
 }
 
 #[cfg(test)]
@@ -59,21 +60,27 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 14);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(14));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 240);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(240));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 34);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(34));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 955);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(955));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("..a.\n.#..").unwrap_err();
+        assert_eq!(err.to_string(), "day 8, line 2: column 2: unexpected '#' (expected '.' or an antenna): \".#..\"");
     }
 }
@@ -1,22 +1,32 @@
 use std::{thread, time};
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 9;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(9) }
 }
 
 #[derive(Clone,Debug)]
-enum Chunk {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Chunk {
     File { size: u8, id: i32 },
     Free { size: u8 }
 }
 
-fn parse(input: &str) -> Vec<Chunk> {
+pub fn parse(input: &str) -> Result<Vec<Chunk>, ParseError> {
     let mut disk: Vec<Chunk> = Vec::new();
     let mut file = true;
     let mut file_id = 0;
-    for c in input.chars() {
-        if let Some(n) = c.to_digit(10) {
+    for line in crate::helpers::lines(DAY, input) {
+        for c in line.text.trim_end().chars() {
+            let n = c.to_digit(10).ok_or_else(|| line.error(format!("expected a digit, got {:?}", c)))?;
             if file {
                 disk.push(Chunk::File { size: n as u8, id: file_id });
                 file_id += 1;
@@ -26,15 +36,17 @@ fn parse(input: &str) -> Vec<Chunk> {
             file = !file;
         }
     }
-    disk
+    if disk.is_empty() {
+        return Err(ParseError::new(DAY, 0, "", "empty disk map"));
+    }
+    Ok(disk)
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let disk = parse(input);
+pub fn part_1(disk: &[Chunk]) -> Result<i64, Error> {
 
     // break each chunk into one block chunks, since we need to move them independently
     let mut fragmented: Vec<Chunk> = Vec::new();
-    for chunk in &disk {
+    for chunk in disk {
         match chunk {
             Chunk::Free { size } => {
                 for _ in (0..*size) {
@@ -51,14 +63,13 @@ pub fn part_1(input: &str) -> i64 {
 
     fragmented = frontload(fragmented);
     //print(&fragmented);
-    checksum(fragmented)
+    Ok(checksum(fragmented))
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let mut disk = parse(input);
-    disk = frontload(disk);
+pub fn part_2(disk: &[Chunk]) -> Result<i64, Error> {
+    let disk = frontload(disk.to_vec());
     //print(&disk);
-    checksum(disk)
+    Ok(checksum(disk))
 }
 
 // Generalized algorithm for both parts 1 & 2, that moves chunks of files towards
@@ -124,7 +135,7 @@ fn checksum(disk: Vec<Chunk>) -> i64 {
             Chunk::File { size, id } => {
                 // checksum is calculated from each block in the chunk
                 for i in (0..size) {
-                    checksum += ((ptr as i64) + i as i64) * (id as i64);
+                    checksum += (ptr + i as i64) * (id as i64);
                 }
                 ptr += size as i64;
             }
@@ -134,7 +145,7 @@ fn checksum(disk: Vec<Chunk>) -> i64 {
 }
 
 fn print(disk: &Vec<Chunk>) {
-    println!("");
+    println!();
     for chunk in disk {
         match chunk {
             Chunk::Free { size } => {
@@ -145,8 +156,8 @@ fn print(disk: &Vec<Chunk>) {
             }
         }
     }
-    println!("");
-    println!("");
+    println!();
+    println!();
 }
 
 // doesn't seem to affect solution speed
@@ -171,21 +182,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 1928);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(1928));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 6448989155953);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(6448989155953));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 2858);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(2858));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 6476642796832);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(6476642796832));
     }
 }
@@ -1,8 +1,10 @@
+use crate::error::{Error, ParseError};
 use crate::grid::Grid;
 use std::collections::HashMap;
 
+const DAY: u32 = 10;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
     pub const SAMPLE3: &str = include_str!("sample3");
@@ -11,54 +13,57 @@ pub mod inputs {
     pub const P2_SAMPLE: &str = include_str!("p2_sample");
     pub const P2_SAMPLE2: &str = include_str!("p2_sample2");
     pub const P2_SAMPLE3: &str = include_str!("p2_sample3");
+
+    pub const ALL: &[(&str, &str)] = &[
+        ("sample", SAMPLE),
+        ("sample2", SAMPLE2),
+        ("sample3", SAMPLE3),
+        ("sample4", SAMPLE4),
+        ("sample_large", SAMPLE_LARGE),
+        ("p2_sample", P2_SAMPLE),
+        ("p2_sample2", P2_SAMPLE2),
+        ("p2_sample3", P2_SAMPLE3),
+    ];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(10) }
 }
 
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
+// heights, with anything that isn't a digit (the samples use '.') too high to ever step onto
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    // some samples have '.' for ground that's too steep to reach from anywhere
+    Grid::try_parse_with(DAY, input, u8::MAX, |c| match (c, c.to_digit(10)) {
+        (_, Some(h)) => Ok(h as u8),
+        ('.', None) => Ok(u8::MAX),
+        _ => Err(format!("unexpected {:?} (expected a height or '.')", c)),
+    })
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let grid: Grid<char> = parse(input);
+pub fn part_1(grid: &Grid<u8>) -> Result<i32, Error> {
     let mut trails = HashMap::new();
     for (c, x, y) in grid.iter() {
-        if c != Some('0') { continue; }
+        if *c != 0 { continue; }
         trails.insert((x,y), Vec::<(i32, i32)>::new());
         let peaks: &mut Vec<(i32, i32)> = trails.get_mut(&(x,y)).unwrap();
-        push_peaks(x, y, &grid, peaks);
+        push_peaks(x, y, grid, peaks);
     }
     let mut score = 0;
     for (head, peaks) in trails {
         score += peaks.len() as i32;
     }
-    score
-}
-
-fn next_height(height: char) -> char {
-    match height {
-        '0' => '1',
-        '1' => '2',
-        '2' => '3',
-        '3' => '4',
-        '4' => '5',
-        '5' => '6',
-        '6' => '7',
-        '7' => '8',
-        '8' => '9',
-        _ => { panic!("unknown next_height for {:?}", height) }
-    }
+    Ok(score)
 }
 
 // recursive fn that looks at a grid cells neighbors, steps up until it reaches height 9,
 // and pushes that peak onto the peaks vec, if it hasn't been found yet.
-fn push_peaks(x: i32, y: i32, grid: &Grid<char>, peaks: &mut Vec<(i32, i32)>) {
-    let height = grid.get(x, y).unwrap();
-    let next = next_height(height);
+fn push_peaks(x: i32, y: i32, grid: &Grid<u8>, peaks: &mut Vec<(i32, i32)>) {
+    let next = grid[(x, y)] + 1;
     for (c, dx, dy) in grid.neighbors(x, y, false).iter() {
         if let Some(h) = c {
             if (*h != next) { continue }
             let x2 = x + dx;
             let y2 = y + dy;
-            if next == '9' {
+            if next == 9 {
                 if !peaks.contains(&(x2, y2)) {
                     peaks.push((x2, y2));
                 }
@@ -70,33 +75,31 @@ fn push_peaks(x: i32, y: i32, grid: &Grid<char>, peaks: &mut Vec<(i32, i32)>) {
 }
 
 // same as part 1, except that instead of tracking unique peaks, we track unique paths to peaks
-pub fn part_2(input: &str) -> i32 {
-    let grid: Grid<char> = parse(input);
+pub fn part_2(grid: &Grid<u8>) -> Result<i32, Error> {
     let mut trails = HashMap::new();
     for (c, x, y) in grid.iter() {
-        if c != Some('0') { continue; }
+        if *c != 0 { continue; }
         trails.insert((x,y), Vec::<Vec<(i32, i32)>>::new());
         let paths: &mut Vec<Vec<(i32, i32)>> = trails.get_mut(&(x,y)).unwrap();
         let path: Vec<(i32, i32)> = Vec::new();
-        push_paths(x, y, &grid, path.clone(), paths);
+        push_paths(x, y, grid, path.clone(), paths);
     }
     let mut score = 0;
     for (head, paths) in trails {
         score += paths.len() as i32;
     }
-    score
+    Ok(score)
 }
 
-fn push_paths(x: i32, y: i32, grid: &Grid<char>, mut path: Vec<(i32, i32)>, paths: &mut Vec<Vec<(i32, i32)>>) {
-    let height = grid.get(x, y).unwrap();
-    let next = next_height(height);
+fn push_paths(x: i32, y: i32, grid: &Grid<u8>, mut path: Vec<(i32, i32)>, paths: &mut Vec<Vec<(i32, i32)>>) {
+    let next = grid[(x, y)] + 1;
     for (c, dx, dy) in grid.neighbors(x, y, false).iter() {
         if let Some(h) = c {
             if (*h != next) { continue }
             let x2 = x + dx;
             let y2 = y + dy;
             path.push((x2, y2));
-            if next == '9' {
+            if next == 9 {
                 if !paths.contains(&path) {
                     paths.push(path.clone());
                 }
@@ -113,57 +116,63 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 1);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(1));
     }
 
     #[test]
     fn part_1_sample2() {
-        assert_eq!(part_1(inputs::SAMPLE2), 2);
+        assert_eq!(part_1(&parse(inputs::SAMPLE2).unwrap()), Ok(2));
     }
 
     #[test]
     fn part_1_sample3() {
-        assert_eq!(part_1(inputs::SAMPLE3), 4);
+        assert_eq!(part_1(&parse(inputs::SAMPLE3).unwrap()), Ok(4));
     }
 
     #[test]
     fn part_1_sample4() {
-        assert_eq!(part_1(inputs::SAMPLE4), 3);
+        assert_eq!(part_1(&parse(inputs::SAMPLE4).unwrap()), Ok(3));
     }
 
     #[test]
     fn part_1_sample_large() {
-        assert_eq!(part_1(inputs::SAMPLE_LARGE), 36);
+        assert_eq!(part_1(&parse(inputs::SAMPLE_LARGE).unwrap()), Ok(36));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 517);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(517));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::P2_SAMPLE), 3);
+        assert_eq!(part_2(&parse(inputs::P2_SAMPLE).unwrap()), Ok(3));
     }
 
     #[test]
     fn part_2_sample2() {
-        assert_eq!(part_2(inputs::P2_SAMPLE2), 13);
+        assert_eq!(part_2(&parse(inputs::P2_SAMPLE2).unwrap()), Ok(13));
     }
 
     #[test]
     fn part_2_sample3() {
-        assert_eq!(part_2(inputs::P2_SAMPLE3), 227);
+        assert_eq!(part_2(&parse(inputs::P2_SAMPLE3).unwrap()), Ok(227));
     }
 
     #[test]
     fn part_2_sample_large() {
-        assert_eq!(part_2(inputs::SAMPLE_LARGE), 81);
+        assert_eq!(part_2(&parse(inputs::SAMPLE_LARGE).unwrap()), Ok(81));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 1116);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(1116));
     }
 
+    #[test]
+    fn parse_errors() {
+        let err = parse("0123\n9.x4").unwrap_err();
+        assert_eq!(err.to_string(), "day 10, line 2: column 3: unexpected 'x' (expected a height or '.'): \"9.x4\"");
+        assert_eq!(parse("0.\n12").unwrap().get(1, 0), Some(u8::MAX));
+    }
 }
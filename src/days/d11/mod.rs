@@ -1,24 +1,35 @@
 use std::str::FromStr;
 use std::collections::HashMap;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 11;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(11) }
 }
 
-fn parse(input: &str) -> Vec<i64> {
-    input
-        .split_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let mut stones = Vec::new();
+    for line in crate::helpers::lines(DAY, input) {
+        for x in line.text.split_whitespace() {
+            stones.push(line.num(x)?);
+        }
+    }
+    Ok(stones)
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mut stones = parse(input);
+pub fn part_1(stones: &[i64]) -> Result<i32, Error> {
+    let mut stones = stones.to_vec();
     for n in (0..25) {
         stones = blink(stones);
     }
-    stones.len() as i32
+    Ok(stones.len() as i32)
 }
 
 fn blink(old_stones: Vec<i64>) -> Vec<i64> {
@@ -47,25 +58,21 @@ fn blink(old_stones: Vec<i64>) -> Vec<i64> {
 // that the 3 rules create repeat stone values at each step. The "blink" algorithm
 // of creating the stones for the next step can be highly optimized by tracking the
 // count of each value at each step, and then sum them after 75 steps to get the final count.
-fn parse_2(input: &str) -> HashMap<i64, i64> {
+fn counts(values: &[i64]) -> HashMap<i64, i64> {
     let mut stones = HashMap::new();
-    let values: Vec<i64> = input
-        .split_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect();
     for value in values {
-        let count = stones.entry(value).or_insert(0);
+        let count = stones.entry(*value).or_insert(0);
         *count += 1;
     }
     stones
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let mut stones = parse_2(input);
+pub fn part_2(stones: &[i64]) -> Result<i64, Error> {
+    let mut stones = counts(stones);
     for n in (0..75) {
         stones = blink_2(stones);
     }
-    stones.values().sum::<i64>() as i64
+    Ok(stones.values().sum::<i64>())
 }
 
 fn blink_2(old_stones: HashMap<i64, i64>) -> HashMap<i64, i64> {
@@ -100,21 +107,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 55312);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(55312));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 189167);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(189167));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 65601038650482);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(65601038650482));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 225253278506288);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(225253278506288));
     }
 }
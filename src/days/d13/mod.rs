@@ -1,14 +1,22 @@
 use regex::Regex;
-use std::str::FromStr;
 use std::collections::HashMap;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 13;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(13) }
 }
 
-#[derive(Debug)]
-struct Game {
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
     // button A
     ax: i64, ay: i64,
     // button B
@@ -17,66 +25,61 @@ struct Game {
     px: i64, py: i64,
 }
 
-fn parse(input: &str) -> Vec<Game> {
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
     let mut games: Vec<Game> = Vec::new();
 
     let a_re = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)").unwrap();
     let b_re = Regex::new(r"Button B: X\+(\d+), Y\+(\d+)").unwrap();
     let p_re = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
 
-    let lines = crate::helpers::to_lines(input);
-
     let mut nums: HashMap<&str, i64> = HashMap::new();
 
-    for line in lines {
-        if a_re.is_match(&line) {
-            let caps = a_re.captures(&line).unwrap();
-            nums.insert( "ax", FromStr::from_str(caps.get(1).unwrap().as_str()).unwrap() );
-            nums.insert( "ay", FromStr::from_str(caps.get(2).unwrap().as_str()).unwrap() );
-        }
-        if b_re.is_match(&line) {
-            let caps = b_re.captures(&line).unwrap();
-            nums.insert( "bx", FromStr::from_str(caps.get(1).unwrap().as_str()).unwrap() );
-            nums.insert( "by", FromStr::from_str(caps.get(2).unwrap().as_str()).unwrap() );
-        }
-        if p_re.is_match(&line) {
-            let caps = p_re.captures(&line).unwrap();
-            nums.insert( "px", FromStr::from_str(caps.get(1).unwrap().as_str()).unwrap() );
-            nums.insert( "py", FromStr::from_str(caps.get(2).unwrap().as_str()).unwrap() );
+    for line in crate::helpers::lines(DAY, input) {
+        if let Some(caps) = a_re.captures(line.text) {
+            nums.insert( "ax", line.num(&caps[1])? );
+            nums.insert( "ay", line.num(&caps[2])? );
+        } else if let Some(caps) = b_re.captures(line.text) {
+            nums.insert( "bx", line.num(&caps[1])? );
+            nums.insert( "by", line.num(&caps[2])? );
+        } else if let Some(caps) = p_re.captures(line.text) {
+            if nums.len() != 4 {
+                return Err(line.error("prize before both buttons"));
+            }
             games.push(Game {
-                ax: *nums.get("ax").unwrap(), ay: *nums.get("ay").unwrap(),
-                bx: *nums.get("bx").unwrap(), by: *nums.get("by").unwrap(),
-                px: *nums.get("px").unwrap(), py: *nums.get("py").unwrap()
+                ax: nums["ax"], ay: nums["ay"],
+                bx: nums["bx"], by: nums["by"],
+                px: line.num(&caps[1])?, py: line.num(&caps[2])?
             });
+            nums.clear();
+        } else if !line.text.is_empty() {
+            return Err(line.error("expected a button or prize"));
         }
     }
 
-    games
+    Ok(games)
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let games = parse(input);
+pub fn part_1(games: &[Game]) -> Result<i64, Error> {
     let mut cost = 0_i64;
-    for mut game in games {
+    for game in games {
         if let Some(game_cost) = cost_to_win(game) {
             cost += game_cost;
         }
     }
-    cost
+    Ok(cost)
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let games = parse(input);
+pub fn part_2(games: &[Game]) -> Result<i64, Error> {
     let mut cost = 0;
     let p_inc = 10000000000000_i64;
-    for mut game in games {
+    for mut game in games.iter().cloned() {
         game.px += p_inc;
         game.py += p_inc;
-        if let Some(game_cost) = cost_to_win(game) {
+        if let Some(game_cost) = cost_to_win(&game) {
             cost += game_cost;
         }
     }
-    cost
+    Ok(cost)
 }
 
 /* Solving as system of equations by elimination.
@@ -106,7 +109,7 @@ pub fn part_2(input: &str) -> i64 {
  * a = (o - (b * n)) / m
  * */
 
-fn cost_to_win(game: Game) -> Option<i64> {
+fn cost_to_win(game: &Game) -> Option<i64> {
     let m = game.ax as f64;
     let n = game.bx as f64;
     let o = game.px as f64;
@@ -132,21 +135,28 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 480);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(480));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 38714);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(38714));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 875318608908);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(875318608908));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 74015623345775);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(74015623345775));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("Button A: X+94, Y+34\nPrize: X=8400, Y=5400").unwrap_err();
+        assert_eq!(err.to_string(), "day 13, line 2: prize before both buttons: \"Prize: X=8400, Y=5400\"");
+        assert_eq!(parse("Button C: X+1, Y+1").unwrap_err().message, "expected a button or prize");
     }
 }
@@ -1,49 +1,83 @@
 use regex::Regex;
-use std::str::FromStr;
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::render::{Color, Overlay, Style};
+use crate::grid::{Grid, Point};
+use std::collections::HashSet;
 use std::{thread, time};
+use crate::solution::parse_positive;
+
+const DAY: u32 = 14;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(14) }
+}
+
+// the size of the space the robots move in, which is given in the puzzle text
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Params {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Params {
+    pub const SAMPLE: Self = Self { width: 11, height: 7 };
+    pub const REAL: Self = Self { width: 101, height: 103 };
+}
+
+impl crate::solution::Params for Params {
+    fn for_input(name: &str) -> Self {
+        match name {
+            "sample" => Self::SAMPLE,
+            _ => Self::REAL,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "width" => self.width = parse_positive(key, value)?,
+            "height" => self.height = parse_positive(key, value)?,
+            _ => return Err(format!("unknown param {:?} (expected width, height)", key)),
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug)]
-struct Robot {
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Robot {
     pub x: i32, pub y: i32,
     pub vx: i32, pub vy: i32
 }
 
-fn parse(input: &str) -> Vec<Robot> {
+pub fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
     let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
-    let lines = crate::helpers::to_lines(input);
     let mut robots: Vec<Robot> = Vec::new();
-    for line in lines {
-        let caps = re.captures(&line).unwrap();
+    for line in crate::helpers::lines(DAY, input) {
+        let caps = line.captures(&re, "p=<x>,<y> v=<vx>,<vy>")?;
         robots.push(Robot {
-            x: FromStr::from_str(caps.get(1).unwrap().as_str()).unwrap(),
-            y: FromStr::from_str(caps.get(2).unwrap().as_str()).unwrap(),
-            vx: FromStr::from_str(caps.get(3).unwrap().as_str()).unwrap(),
-            vy: FromStr::from_str(caps.get(4).unwrap().as_str()).unwrap(),
+            x: line.num(&caps[1])?,
+            y: line.num(&caps[2])?,
+            vx: line.num(&caps[3])?,
+            vy: line.num(&caps[4])?,
         });
     }
-    robots
+    Ok(robots)
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mut robots = parse(input);
-    // the width/height are given separately
-    let (width, height) =
-        if input == inputs::SAMPLE {
-            (11, 7)
-        } else {
-            (101, 103)
-        };
+pub fn part_1(robots: &[Robot], params: &Params) -> Result<i32, Error> {
+    let mut robots = robots.to_vec();
+    let Params { width, height } = *params;
+    let space = space(width, height);
     //draw(&robots, width, height);
 
     for _ in (0..100) {
         for robot in &mut robots {
-            step(robot, width, height);
+            step(robot, &space);
         }
     }
 
@@ -68,73 +102,67 @@ pub fn part_1(input: &str) -> i32 {
         } // else not counted
     }
 
-    q1 * q2 * q3 * q4
+    Ok(q1 * q2 * q3 * q4)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let mut robots = parse(input);
-    // the width/height are given separately
-    let (width, height) =
-        if input == inputs::SAMPLE {
-            (11, 7)
-        } else {
-            (101, 103)
-        };
-
-    let mut i = 0;
-    loop {
-        // some key cells to look for--the border around the xmas tree
-        // remove them as we see them. when vec is empty then easter egg is present.
-        let mut border = vec![ (41,20), (41,21), (41,22), (42,20), (43,20) ];
-
-        i += 1;
+// The robots arrange themselves into a picture of a christmas tree, which has a frame around it, so
+// look for the first second with a long row of robots side by side. They're all back where they
+// started after `width * height` seconds, so if there's no picture by then there never will be.
+pub fn part_2(robots: &[Robot], params: &Params) -> Result<i32, Error> {
+    let mut robots = robots.to_vec();
+    let Params { width, height } = *params;
+    let space = space(width, height);
+
+    for i in 1..=width * height {
         for robot in &mut robots {
-            step(robot, width, height);
-            if let Some(i) = border.iter().position(|&(x,y)| x==robot.x && y==robot.y) {
-                border.remove(i);
-            }
+            step(robot, &space);
         }
-
-        if border.is_empty() {
+        if longest_row(&robots) >= TREE_ROW {
             // ensure it's the full image
             //draw(&robots, width, height);
-            break;
+            return Ok(i);
         }
-
-        // first spotted tree around this time. paused to get some key cells to look for.
-        /*if i >= 28150 && i % 5 == 0 {
-            draw(&robots, width, height);
-            println!("\n");
-            println!("i={:?}", i);
-            return 0;
-        }*/
     }
-    i
+    Err(Error::NoAnswer("the robots never make a picture".to_string()))
 }
 
-fn step(robot: &mut Robot, width: i32, height: i32) {
-    robot.x += robot.vx;
-    robot.y += robot.vy;
-    if robot.x > width - 1 {
-        robot.x -= width;
-    } else if robot.x < 0 {
-        robot.x += width;
-    }
-    if robot.y > height - 1 {
-        robot.y -= height;
-    } else if robot.y < 0 {
-        robot.y += height;
+// how many robots side by side in a row means the picture is there, which is much longer than they
+// ever line up by chance
+const TREE_ROW: usize = 10;
+
+// the most robots side by side in any row
+fn longest_row(robots: &[Robot]) -> usize {
+    let taken: HashSet<(i32, i32)> = robots.iter().map(|r| (r.x, r.y)).collect();
+    let mut longest = 0;
+    for &(x, y) in &taken {
+        // only count from the left end of each run
+        if taken.contains(&(x - 1, y)) { continue }
+        let run = (x..).take_while(|x| taken.contains(&(*x, y))).count();
+        longest = longest.max(run);
     }
+    longest
+}
+
+// the space the robots move in, which wraps around at the edges
+fn space(width: i32, height: i32) -> Grid<char> {
+    Grid::fresh('.', width, height).wrapping(true)
 }
 
-fn draw(robots: &Vec<Robot>, width: i32, height: i32) {
+fn step(robot: &mut Robot, space: &Grid<char>) {
+    let p = space.wrap_point(Point::new(robot.x + robot.vx, robot.y + robot.vy));
+    robot.x = p.x;
+    robot.y = p.y;
+}
+
+fn draw(robots: &[Robot], width: i32, height: i32) {
     thread::sleep(time::Duration::from_millis(1000));
-    let mut grid = Grid::fresh('.', width, height);
-    for robot in robots {
-        grid.set('X', robot.x, robot.y);
-    }
-    println!("\n");
-    grid.draw(true);
+    let grid = Grid::fresh('.', width, height);
+    let robots = robots.iter().map(|r| Point::new(r.x, r.y));
+    grid.render()
+        .clear(true)
+        .style(|_| Style::fg(Color::Grey))
+        .overlay(Overlay::glyph(robots, 'X', Style::fg(Color::Green).bold()))
+        .print();
 }
 
 #[cfg(test)]
@@ -143,21 +171,42 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 12);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok(12));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 211773366);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(211773366));
     }
 
-    /*#[test]
+    #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 0);
-    }*/
+        // too few robots to make a picture, so every arrangement comes round again without one
+        let err = || Err(Error::NoAnswer("the robots never make a picture".to_string()));
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), err());
+        assert_eq!(part_2(&parse("").unwrap(), &Params::SAMPLE), err());
+    }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 7344);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(7344));
+    }
+
+    #[test]
+    fn params() {
+        use crate::solution::Params as _;
+        let mut params = Params::SAMPLE;
+        assert_eq!(params.set("width", "5"), Ok(()));
+        assert_eq!(params.width, 5);
+        assert!(params.set("width", "0").is_err());
+        assert!(params.set("height", "-7").is_err());
+    }
+
+    #[test]
+    fn step_wraps() {
+        // faster than the space is wide, so it wraps more than once
+        let mut robot = Robot { x: 2, y: 4, vx: 25, vy: -16 };
+        step(&mut robot, &space(11, 7));
+        assert_eq!((robot.x, robot.y), (5, 2));
     }
 }
@@ -1,231 +1,232 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::{Dir4, Grid, Point};
+
+const DAY: u32 = 15;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE), ("sample2", SAMPLE2)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(15) }
 }
 
-fn parse(input: &str) -> (Grid<char>, Vec<Dir>) {
-    let lines = crate::helpers::to_lines(input);
+pub fn parse(input: &str) -> Result<(Grid<char>, Vec<Dir4>), ParseError> {
     let mut grid_lines: Vec<String> = Vec::new();
-    let mut moves: Vec<Dir> = Vec::new();
+    let mut moves: Vec<Dir4> = Vec::new();
     let mut done_grid = false;
 
-    for line in &lines {
+    for line in crate::helpers::lines(DAY, input) {
         if !done_grid {
-            if line == "" {
+            if line.text.is_empty() {
                 done_grid = true;
             } else {
-                grid_lines.push(line.clone());
+                grid_lines.push(line.text.to_string());
             }
             continue;
         }
 
-        for c in line.chars() {
-            let dir = match c {
-                '^' => Dir::U,
-                '>' => Dir::R,
-                'v' => Dir::D,
-                '<' => Dir::L,
-                _ => panic!("unexpected char for dir {}", c)
-            };
+        for c in line.text.chars() {
+            let dir = Dir4::from_arrow(c)
+                .ok_or_else(|| line.error(format!("unexpected char for dir {:?}", c)))?;
             moves.push(dir);
         }
     }
 
-    (Grid::from_lines(grid_lines), moves)
-}
-
-#[derive(Debug)]
-pub enum Dir { U, D, L, R }
-
-fn next_pos(pos: (i32, i32), dir: &Dir) -> (i32, i32) {
-    let offset = match dir {
-        Dir::U => (0, -1),
-        Dir::R => (1, 0),
-        Dir::D => (0, 1),
-        Dir::L => (-1, 0)
-    };
-    (pos.0 + offset.0, pos.1 + offset.1)
+    // the warehouse starts on line 1, so its errors are on the right lines
+    let warehouse = grid_lines.join("\n");
+    let grid = Grid::parse_chars(DAY, &warehouse, "'#', '.', 'O' or '@'", |c| "#.O@".contains(c))?;
+    crate::helpers::one_marker(DAY, &warehouse, '@', "robot '@' in the warehouse")?;
+    Ok((grid, moves))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (mut grid, moves) = parse(input);
+pub fn part_1(parsed: &(Grid<char>, Vec<Dir4>)) -> Result<i32, Error> {
+    let (mut grid, moves) = parsed.clone();
 
     for dir in moves {
-        let pos = grid.find('@').unwrap();
-        grid = move_dir(grid.clone(), pos, &dir);
+        let pos = grid.find_point('@').unwrap();
+        move_dir(&mut grid, pos, dir);
     }
 
     let mut gps_sum = 0;
-    for (oc, x, y) in grid.iter() {
-        if oc.unwrap() == 'O' {
+    for (c, x, y) in grid.iter() {
+        if *c == 'O' {
             gps_sum += 100 * y + x;
         }
     }
-    gps_sum
+    Ok(gps_sum)
 }
 
-pub fn move_dir(mut grid: Grid<char>, pos: (i32, i32), dir: &Dir) -> Grid<char> {
-    let p = next_pos(pos, dir);
-    let mut c = grid.get(p.0, p.1).unwrap();
-    match c {
-        '#' => { return grid },
-        '@' => panic!("trying to move into robot"),
-        'O' => {
+pub fn move_dir(grid: &mut Grid<char>, pos: Point, dir: Dir4) {
+    let p = pos.step(dir);
+    match grid.get_at(p) {
+        Some('O') => {
             // try to move the box
-            grid = move_dir(grid, p, dir);
+            move_dir(grid, p, dir);
             // we'll move below, if possible
         }
-        '.' => {
+        Some('.') => {
             // we'll move below
         }
-        _ => panic!("unexpected char {}", c),
+        // a wall, or the edge of a warehouse without one
+        _ => { return },
     }
 
     // try to move, might not be able to if there was a box and it couldn't move
-    c = grid.get(p.0, p.1).unwrap();
-    if c == '.' {
-        let s = grid.get(pos.0, pos.1).unwrap();
-        grid.set('.', pos.0, pos.1);
-        grid.set(s, p.0, p.1);
+    if grid.get_at(p) == Some('.') {
+        let s = grid.get_at(pos).unwrap();
+        grid.set_at('.', pos);
+        grid.set_at(s, p);
     }
-    return grid;
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let (mut grid0, moves) = parse(input);
-
-    // build a copy of the grid that is twice as wide
-    let mut grid = Grid::fresh('.', grid0.get_width() * 2, grid0.get_height());
-    for (oc, x, y) in grid0.iter() {
-        match oc.unwrap() {
-            '#' => {
-                grid.set('#', x*2, y);
-                grid.set('#', x*2+1, y);
-            }
-            '@' => {
-                grid.set('@', x*2, y);
-                grid.set('.', x*2+1, y);
-            }
-            'O' => {
-                grid.set('[', x*2, y);
-                grid.set(']', x*2+1, y);
-            }
-            '.' => {
-                grid.set('.', x*2, y);
-                grid.set('.', x*2+1, y);
-            }
-            c => panic!("unexpected char {}", c)
-        }
-    }
+pub fn part_2(parsed: &(Grid<char>, Vec<Dir4>)) -> Result<i32, Error> {
+    let (grid0, moves) = parsed;
+    let mut grid = wide(grid0);
 
-    for dir in moves {
-        let pos = grid.find('@').unwrap();
-        grid = move_dir_2(grid.clone(), pos, &dir);
+    for &dir in moves {
+        let pos = grid.find_point('@').unwrap();
+        move_dir_2(&mut grid, pos, dir);
     }
 
     let mut gps_sum = 0;
-    for (oc, x, y) in grid.iter() {
-        if oc.unwrap() == '[' {
+    for (c, x, y) in grid.iter() {
+        if *c == '[' {
             gps_sum += 100 * y + x;
         }
     }
-    gps_sum
+    Ok(gps_sum)
+}
+
+// build a copy of the grid that is twice as wide
+pub fn wide(grid: &Grid<char>) -> Grid<char> {
+    // `parse` only lets through these, so anything else is a wall or floor that just doubles
+    grid.widen(|c| match c {
+        '@' => vec!['@', '.'],
+        'O' => vec!['[', ']'],
+        &c => vec![c, c],
+    })
 }
 
 // recursive fn to check if object can move in direction
-pub fn can_move_2(grid: &Grid<char>, pos: (i32, i32), dir: &Dir) -> bool {
-    let p = next_pos(pos, dir);
-    let c = grid.get(p.0, p.1).unwrap();
-    match c {
-        '#' => return false,
-        '@' => panic!("trying to move into robot"),
-        '[' => {
-            return match dir {
+pub fn can_move_2(grid: &Grid<char>, pos: Point, dir: Dir4) -> bool {
+    let p = pos.step(dir);
+    match grid.get_at(p) {
+        Some('[') => {
+            match dir {
                 // when moving boxes U/D, have to check if the other piece can move U/D too.
-                Dir::U | Dir::D => can_move_2(grid, p, dir) && can_move_2(grid, (p.0+1, p.1), dir),
+                Dir4::U | Dir4::D => can_move_2(grid, p, dir) && can_move_2(grid, p.step(Dir4::R), dir),
                 // moving away from other piece; it can move into this spot if this piece can move.
-                Dir::L => can_move_2(grid, p, dir),
+                Dir4::L => can_move_2(grid, p, dir),
                 // moving into other piece; just check the other piece as if it were another object.
-                Dir::R => can_move_2(grid, (p.0+1, p.1), dir)
+                Dir4::R => can_move_2(grid, p.step(Dir4::R), dir)
             }
         },
-        ']' => {
-            return match dir {
-                Dir::U | Dir::D => can_move_2(grid, (p.0-1, p.1), dir) && can_move_2(grid, p, dir),
-                Dir::L => can_move_2(grid, (p.0-1, p.1), dir),
-                Dir::R => can_move_2(grid, p, dir)
+        Some(']') => {
+            match dir {
+                Dir4::U | Dir4::D => can_move_2(grid, p.step(Dir4::L), dir) && can_move_2(grid, p, dir),
+                Dir4::L => can_move_2(grid, p.step(Dir4::L), dir),
+                Dir4::R => can_move_2(grid, p, dir)
             }
         },
-        '.' => return true,
-        _ => panic!("unexpected char {}", c),
+        Some('.') => true,
+        // a wall, or the edge of a warehouse without one
+        _ => false,
     }
 }
-pub fn move_dir_2(mut grid: Grid<char>, pos: (i32, i32), dir: &Dir) -> Grid<char> {
+pub fn move_dir_2(grid: &mut Grid<char>, pos: Point, dir: Dir4) {
     // first check self and anything in the way can move.
     // it's resursive, so we only need to check once, on the initial move of the robot.
     // for the boxes, we will have already checked.
-    let s = grid.get(pos.0, pos.1).unwrap();
-    if s == '@' {
-        if !can_move_2(&grid, pos, dir) {
-            return grid;
-        }
-    }
-
-    // move anything that is in the way
-    let p = next_pos(pos, dir);
-    let c = grid.get(p.0, p.1).unwrap();
-    match c {
-        '[' | ']' => {
-            match dir {
-                Dir::U | Dir::D => {
-                    let o = if c == '[' { (p.0+1,p.1) } else { (p.0-1,p.1) };
-                    grid = move_dir_2(grid, o, dir);
-                    grid = move_dir_2(grid, p, dir);
-                }
-                _ => {
-                    grid = move_dir_2(grid, p, dir);
-                }
+    let s = grid.get_at(pos).unwrap();
+    if s == '@' && !can_move_2(grid, pos, dir) {
+        return;
+    }
+
+    // move anything that is in the way. otherwise it's floor, since `can_move_2` has checked it
+    // isn't a wall
+    let p = pos.step(dir);
+    if let Some(c @ ('[' | ']')) = grid.get_at(p) {
+        match dir {
+            Dir4::U | Dir4::D => {
+                let o = if c == '[' { p.step(Dir4::R) } else { p.step(Dir4::L) };
+                move_dir_2(grid, o, dir);
+                move_dir_2(grid, p, dir);
+            }
+            _ => {
+                move_dir_2(grid, p, dir);
             }
         }
-        '.' => { /* nothing to move */ }
-        _ => panic!("trying to move into {}", c),
     }
 
     // move self
-    grid.set('.', pos.0, pos.1);
-    grid.set(s, p.0, p.1);
-    return grid;
+    grid.set_at('.', pos);
+    grid.set_at(s, p);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::snapshot;
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 2028);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(2028));
     }
 
     #[test]
     fn part_1_sample2() {
-        assert_eq!(part_1(inputs::SAMPLE2), 10092);
+        assert_eq!(part_1(&parse(inputs::SAMPLE2).unwrap()), Ok(10092));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 1495147);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(1495147));
     }
 
     #[test]
     fn part_2_sample2() {
-        assert_eq!(part_2(inputs::SAMPLE2), 9021);
+        assert_eq!(part_2(&parse(inputs::SAMPLE2).unwrap()), Ok(9021));
+    }
+
+    #[test]
+    fn move_dir_2_snapshots() {
+        // pushing up a stack of boxes, offset so one box pushes two
+        let mut grid = Grid::from_str("##########\n##......##\n##..[]..##\n##.[][].##\n##..[]..##\n##...@..##\n##########");
+        let pos = grid.find_point('@').unwrap();
+        move_dir_2(&mut grid, pos, Dir4::U);
+        snapshot::assert_snapshot(DAY, "push_up", &grid);
+
+        let (grid, moves) = parse(inputs::SAMPLE2).unwrap();
+        let mut grid = wide(&grid);
+        for dir in moves {
+            let pos = grid.find_point('@').unwrap();
+            move_dir_2(&mut grid, pos, dir);
+        }
+        snapshot::assert_snapshot(DAY, "sample2_part_2", &grid);
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 1524905);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(1524905));
+    }
+
+    #[test]
+    fn no_walls() {
+        // the edge of the warehouse stops the robot and boxes like a wall would
+        assert_eq!(part_1(&parse("@O\n\n>>^").unwrap()), Ok(1));
+        assert_eq!(part_2(&parse("@O\n\n>>>^").unwrap()), Ok(2));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("#@.#\n\n<>x").unwrap_err();
+        assert_eq!(err.to_string(), "day 15, line 3: unexpected char for dir 'x': \"<>x\"");
+        assert_eq!(parse("#..#\n\n<").unwrap_err().to_string(), "day 15: no robot '@' in the warehouse");
+        let err = parse("#@.#\n#.[#\n\n<").unwrap_err();
+        assert_eq!(err.to_string(), "day 15, line 2: column 3: unexpected '[' (expected '#', '.', 'O' or '@'): \"#.[#\"");
+        assert_eq!(parse("#@.#\n#@.#\n\n<").unwrap_err().message, "more than one robot '@' in the warehouse");
     }
 }
@@ -1,90 +1,50 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::render::{Color, Overlay, Style};
+use crate::grid::search::dijkstra;
+use crate::grid::{Dir4, Grid, Point};
 use std::{thread, time};
 
+const DAY: u32 = 16;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
-}
-
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
-}
 
-#[derive(Clone,Debug,PartialEq)]
-pub enum Dir { U, D, L, R, Z } // Z == unknown
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE), ("sample2", SAMPLE2)];
 
-#[derive(Debug)]
-struct Node {
-    c: char,
-    x: i32,
-    y: i32,
-    cost: i32,
-    dir: Dir
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(16) }
 }
 
-// solved with Dijkstra's algorithm
-pub fn part_1(input: &str) -> i32 {
-    let mut grid = parse(input);
-
-    // build a list of unvisited nodes from the grid
-    let mut unvisited: Vec<Node> = Vec::new();
-    for (oc, x, y) in grid.iter() {
-        let c = oc.unwrap();
-        match c {
-            '.' | 'E' => {
-                unvisited.push(Node { c, x, y, cost: i32::MAX, dir: Dir::Z });
-            }
-            'S' => {
-                unvisited.push(Node { c, x, y, cost: 0, dir: Dir::R });
-            }
-            _ => ()
-        }
-    }
-
-    let mut visited: Vec<Node> = Vec::new();
-
-    // sort unvisited to move Start to the front
-    unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-    let mut cur = unvisited.remove(0);
-
-    // loop until we find End
-    loop {
-        for (oc, dx, dy) in grid.neighbors(cur.x, cur.y, false) {
-            let x = cur.x + dx;
-            let y = cur.y + dy;
-            if let Some(i) = unvisited.iter().position(|n| n.x == x && n.y == y) {
-                let dir = match (dx,dy) {
-                    (0, -1) => Dir::U,
-                    (1, 0) => Dir::R,
-                    (0, 1) => Dir::D,
-                    (-1, 0) => Dir::L,
-                    _ => panic!("unexpected offsets {:?},{:?}", dx, dy)
-                };
-                let mut cost = cur.cost + 1;
-                if dir != cur.dir { cost += 1000; }
-                let mut node = unvisited.remove(i);
-                if cost < node.cost {
-                    node.cost = cost;
-                    node.dir = dir;
-                }
-                unvisited.push(node);
-            }
-        }
-
-        unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-        cur = unvisited.remove(0);
-        if cur.c == 'E' { break }
-        if unvisited.len() == 0 { panic!("visited all nodes!") }
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let grid = Grid::parse_chars(DAY, input, "'#', '.', 'S' or 'E'", |c| "#.SE".contains(c))?;
+    for c in ['S', 'E'] {
+        crate::helpers::one_marker(DAY, input, c, &format!("{:?} in the maze", c))?;
     }
+    Ok(grid)
+}
 
-    cur.cost
+// solved with Dijkstra's algorithm, where the state is the reindeer's position and facing
+pub fn part_1(grid: &Grid<char>) -> Result<i32, Error> {
+    let start = (grid.find_point('S').unwrap(), Dir4::R);
+
+    let search = dijkstra(
+        start,
+        |&(p, facing): &(Point, Dir4)| {
+            p.neighbors4()
+                .filter(|&(_, n)| grid.get_at(n).is_some_and(|c| c != '#'))
+                // stepping any way but straight ahead means turning first
+                .map(move |(dir, n)| ((n, dir), if dir == facing { 1 } else { 1001 }))
+                .collect::<Vec<_>>()
+        },
+        |&(p, _)| grid[p] == 'E',
+    );
+    search.goal_cost().ok_or_else(|| Error::NoAnswer("no way through the maze".to_string()))
 }
 
-pub fn part_2(input: &str) -> i32 {
+pub fn part_2(grid: &Grid<char>) -> Result<i32, Error> {
     // use our part_1 solution to get the best path
-    let best_cost = part_1(input);
-    let mut grid = parse(input);
+    let best_cost = part_1(grid)?;
 
     // grid for maintaining best costs for each cell
     // `i32::MAX - 1001` gives us room to to add 1001 to the values, the cost of 1 step and turn
@@ -94,26 +54,25 @@ pub fn part_2(input: &str) -> i32 {
     let mut path = Path {
         steps: Vec::new(),
         cost: 0,
-        dir: Dir::R
+        dir: Dir4::R
     };
 
-    path.steps.push(grid.find('S').unwrap());
+    path.steps.push(grid.find_point('S').unwrap());
     todo_paths.push(path);
     let mut done_paths: Vec<Path> = Vec::new();
 
     let mut i = 0;
-    while todo_paths.len() > 0 {
+    while !todo_paths.is_empty() {
         i += 1;
 
         // shift off the first path to work on it
         let path = todo_paths.swap_remove(0);
 
         /*if i % 10000 == 0 {
-            let mut grid2 = grid.clone();
-            for (a,b) in path.steps.clone() {
-                grid2.set('*', a, b);
-            }
-            grid2.draw(true);
+            grid.render()
+                .clear(true)
+                .overlay(Overlay::path(&path.steps, Style::fg(Color::Yellow)))
+                .print();
             thread::sleep(time::Duration::from_millis(100));
         }*/
 
@@ -125,19 +84,19 @@ pub fn part_2(input: &str) -> i32 {
 
         // skip if cost of the last step is more that a step and turn than
         // the observed best for this cell, so far
-        let last_cur_best = costs_grid.get(last.0, last.1).unwrap();
+        let last_cur_best = costs_grid.get_at(*last).unwrap();
         if path.cost > last_cur_best + 1001 { continue }
 
         // mark cost on this cell if better than what we have observed
         if path.cost < last_cur_best {
-            costs_grid.set(path.cost, last.0, last.1);
+            costs_grid.set_at(path.cost, *last);
         }
 
-        pathfind(&grid, &mut todo_paths, &mut done_paths, path);
+        pathfind(grid, &mut todo_paths, &mut done_paths, path);
     }
 
     // gather all the unique cells on all best paths
-    let mut unique: Vec<(i32, i32)> = Vec::new();
+    let mut unique: Vec<Point> = Vec::new();
     for done_path in done_paths {
         for step in done_path.steps {
             if !unique.contains(&step) {
@@ -146,61 +105,45 @@ pub fn part_2(input: &str) -> i32 {
         }
     }
 
-    unique.len() as i32
+    Ok(unique.len() as i32)
 }
 
 #[derive(Clone,Debug)]
 struct Path {
-    pub steps: Vec<(i32, i32)>,
+    pub steps: Vec<Point>,
     pub cost: i32,
-    pub dir: Dir
+    pub dir: Dir4
 }
 
 // Take a path, and for each of its potential next steps, construct a new path and push it onto
 // `todo_paths`. If it reaches the end, push it onto `done_paths`.
 fn pathfind(grid: &Grid<char>, todo_paths: &mut Vec<Path>, done_paths: &mut Vec<Path>, mut path: Path) {
-    let (x,y) = path.steps.last().unwrap();
-
-    let mut neighbors = grid.neighbors(*x, *y, false);
+    let last = *path.steps.last().unwrap();
 
-    for (oc, dx, dy) in neighbors {
-        let p = (*x + dx, *y + dy);
+    for (dir, p) in last.neighbors4() {
         if path.steps.contains(&p) { continue }
-        let c = oc.unwrap();
-
-        match c {
-            'S' | '#'  => (),
-            'E' | '.'  => {
-                let mut path = path.clone();
-                path.steps.push(p);
-                path.cost += 1;
-                let dir = match (dx, dy) {
-                    (-1,0) => Dir::L, (1,0) => Dir::R, (0,-1) => Dir::U, (0,1) => Dir::D,
-                    _ => panic!("unexpected offset {:?},{:?}", dx, dy)
-                };
-                if dir != path.dir {
-                    path.cost += 1000;
-                    path.dir = dir;
-                }
-
-                match c {
-                    '.' => {
-                        todo_paths.push(path);
-                        todo_paths.sort_by(|a, b| {
-                            a.cost.cmp(&b.cost)
-                        });
-                    },
-                    'E' => {
-                        // println!("END");
-                        done_paths.push(path);
-                        done_paths.sort_by(|a, b| {
-                            a.cost.cmp(&b.cost)
-                        });
-                    }
-                    _ => panic!("unexpected char {:?}", oc)
-                }
-            },
-            _ => panic!("unexpected char {:?}", oc)
+        // the walls, the start, or the edge of a maze without walls
+        let Some(c @ ('E' | '.')) = grid.get_at(p) else { continue };
+
+        let mut path = path.clone();
+        path.steps.push(p);
+        path.cost += 1;
+        if dir != path.dir {
+            path.cost += 1000;
+            path.dir = dir;
+        }
+
+        if c == '.' {
+            todo_paths.push(path);
+            todo_paths.sort_by(|a, b| {
+                a.cost.cmp(&b.cost)
+            });
+        } else {
+            // println!("END");
+            done_paths.push(path);
+            done_paths.sort_by(|a, b| {
+                a.cost.cmp(&b.cost)
+            });
         }
     }
 }
@@ -211,31 +154,49 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 7036);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(7036));
     }
 
     #[test]
     fn part_1_sample2() {
-        assert_eq!(part_1(inputs::SAMPLE2), 11048);
+        assert_eq!(part_1(&parse(inputs::SAMPLE2).unwrap()), Ok(11048));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 122492);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(122492));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 45);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(45));
     }
 
     #[test]
     fn part_2_sample2() {
-        assert_eq!(part_2(inputs::SAMPLE2), 64);
+        assert_eq!(part_2(&parse(inputs::SAMPLE2).unwrap()), Ok(64));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 520);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(520));
+    }
+
+    #[test]
+    fn no_way_through() {
+        let grid = parse("#####\n#S#E#\n#####").unwrap();
+        assert_eq!(part_1(&grid), Err(Error::NoAnswer("no way through the maze".to_string())));
+        assert!(part_2(&grid).is_err());
+        // no walls around the edge, so the reindeer has to stay inside
+        assert_eq!(part_1(&parse("S.E").unwrap()), Ok(2));
+        assert_eq!(part_2(&parse("S.E").unwrap()), Ok(3));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("#S.#\n#.x#\n#.E#").unwrap_err();
+        assert_eq!(err.to_string(), "day 16, line 2: column 3: unexpected 'x' (expected '#', '.', 'S' or 'E'): \"#.x#\"");
+        assert_eq!(parse("#S.#").unwrap_err().to_string(), "day 16: no 'E' in the maze");
+        assert_eq!(parse("SE\nE.").unwrap_err(), ParseError::new(DAY, 2, "E.", "more than one 'E' in the maze"));
     }
 }
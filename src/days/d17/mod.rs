@@ -1,8 +1,12 @@
 use itertools::Itertools;
 use std::{thread, time};
 
+use crate::error::{Error, ParseError};
+use crate::helpers::Line;
+
+const DAY: u32 = 17;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
     pub const SMALL: &str = include_str!("small");
@@ -10,13 +14,27 @@ pub mod inputs {
     pub const SMALL3: &str = include_str!("small3");
     pub const SMALL4: &str = include_str!("small4");
     pub const SMALL5: &str = include_str!("small5");
+
+    pub const ALL: &[(&str, &str)] = &[
+        ("sample", SAMPLE),
+        ("sample2", SAMPLE2),
+        ("small", SMALL),
+        ("small2", SMALL2),
+        ("small3", SMALL3),
+        ("small4", SMALL4),
+        ("small5", SMALL5),
+    ];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(17) }
 }
 
 #[derive(Clone,Debug)]
-struct Computer {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Computer {
     pub reg_a: i64,
-    reg_b: i64,
-    reg_c: i64,
+    pub reg_b: i64,
+    pub reg_c: i64,
     pub program: Vec<i64>,
     ptr: i64,
     pub out: Vec<i64>,
@@ -34,43 +52,44 @@ impl Computer {
         }
     }
 
-    pub fn run(&mut self) {
-        loop {
-            match self.program.get(self.ptr as usize) {
-                Some(opcode) => {
-                    let operand = self.program.get((self.ptr + 1) as usize).unwrap();
-                    match opcode {
-                        0 => self.adv(*operand),
-                        1 => self.bxl(*operand),
-                        2 => self.bst(*operand),
-                        3 => self.jnz(*operand),
-                        4 => self.bxc(*operand),
-                        5 => self.out(*operand),
-                        6 => self.bdv(*operand),
-                        7 => self.cdv(*operand),
-                        _ => panic!("unexpected opcode {:?}!", opcode)
-                    }
-                }
-                None => break // halt
+    // Run until the pointer runs off the end of the program. `parse` checks the program, but a jump
+    // can still land where there's no operand, or a division can go out of range.
+    pub fn run(&mut self) -> Result<(), Error> {
+        while let Some(&opcode) = self.program.get(self.ptr as usize) {
+            let operand = *self.program.get((self.ptr + 1) as usize)
+                .ok_or_else(|| self.error(format!("no operand for opcode {} at {}", opcode, self.ptr)))?;
+            match opcode {
+                0 => self.adv(operand)?,
+                1 => self.bxl(operand),
+                2 => self.bst(operand)?,
+                3 => self.jnz(operand),
+                4 => self.bxc(operand),
+                5 => self.out(operand)?,
+                6 => self.bdv(operand)?,
+                7 => self.cdv(operand)?,
+                _ => return Err(self.error(format!("unexpected opcode {}", opcode))),
             }
         }
+        Ok(())
     }
 
     fn move_ptr(&mut self) { self.ptr += 2; }
 
-    fn adv(&mut self, operand: i64) {
-        self.reg_a = self.reg_a / 2_i64.pow(self.combo(operand).try_into().unwrap());
+    fn adv(&mut self, operand: i64) -> Result<(), Error> {
+        self.reg_a = self.dv(operand)?;
         self.move_ptr();
+        Ok(())
     }
 
     fn bxl(&mut self, operand: i64) {
-        self.reg_b = self.reg_b ^ operand;
+        self.reg_b ^= operand;
         self.move_ptr();
     }
 
-    fn bst(&mut self, operand: i64) {
-        self.reg_b = self.combo(operand) % 8;
+    fn bst(&mut self, operand: i64) -> Result<(), Error> {
+        self.reg_b = self.combo(operand)? % 8;
         self.move_ptr();
+        Ok(())
     }
 
     fn jnz(&mut self, operand: i64) {
@@ -82,65 +101,103 @@ impl Computer {
     }
 
     fn bxc(&mut self, _operand: i64) {
-        self.reg_b = self.reg_b ^ self.reg_c;
+        self.reg_b ^= self.reg_c;
         self.move_ptr();
     }
 
-    fn out(&mut self, operand: i64) {
-        self.out.push(self.combo(operand) % 8);
+    fn out(&mut self, operand: i64) -> Result<(), Error> {
+        self.out.push(self.combo(operand)? % 8);
         self.move_ptr();
+        Ok(())
     }
 
-    fn bdv(&mut self, operand: i64) {
-        self.reg_b = self.reg_a / 2_i64.pow(self.combo(operand).try_into().unwrap());
+    fn bdv(&mut self, operand: i64) -> Result<(), Error> {
+        self.reg_b = self.dv(operand)?;
         self.move_ptr();
+        Ok(())
     }
 
-    fn cdv(&mut self, operand: i64) {
-        self.reg_c = self.reg_a / 2_i64.pow(self.combo(operand).try_into().unwrap());
+    fn cdv(&mut self, operand: i64) -> Result<(), Error> {
+        self.reg_c = self.dv(operand)?;
         self.move_ptr();
+        Ok(())
     }
 
-    fn combo(&self, operand: i64) -> i64 {
+    // register A divided by 2 to the power of the combo operand, which adv, bdv and cdv share
+    fn dv(&self, operand: i64) -> Result<i64, Error> {
+        let power = self.combo(operand)?;
+        match u32::try_from(power) {
+            // past 2^62 the quotient is 0, but the divisor doesn't fit
+            Ok(power) => Ok(2_i64.checked_pow(power).map_or(0, |d| self.reg_a / d)),
+            Err(_) => Err(self.error(format!("can't divide by 2 to the power of {}", power))),
+        }
+    }
+
+    fn combo(&self, operand: i64) -> Result<i64, Error> {
         match operand {
-            0 => 0,
-            1 => 1,
-            2 => 2,
-            3 => 3,
-            4 => self.reg_a,
-            5 => self.reg_b,
-            6 => self.reg_c,
+            0 => Ok(0),
+            1 => Ok(1),
+            2 => Ok(2),
+            3 => Ok(3),
+            4 => Ok(self.reg_a),
+            5 => Ok(self.reg_b),
+            6 => Ok(self.reg_c),
             // "Combo operand 7 is reserved and will not appear in valid programs."
-            7 => panic!("invalid combo operand 7!"),
-            _ => panic!("unexpected combo operand {:?}!", operand)
+            _ => Err(self.error(format!("invalid combo operand {}", operand))),
         }
     }
 
-    fn output(&self) -> String {
+    fn error(&self, message: String) -> Error {
+        Error::NoAnswer(format!("the program can't run: {}", message))
+    }
+
+    pub fn output(&self) -> String {
         self.out.iter().map(|&n| n.to_string()).join(",")
     }
 }
 
-fn parse(input: &str) -> Computer {
-    let lines = crate::helpers::to_lines(input);
+pub fn parse(input: &str) -> Result<Computer, ParseError> {
+    let lines: Vec<Line> = crate::helpers::lines(DAY, input).collect();
+    if lines.len() < 5 {
+        return Err(ParseError::new(DAY, 0, "", "expected 3 registers, a blank line and a program"));
+    }
 
-    let reg_a: i64 = lines[0].split(": ").collect::<Vec<&str>>()[1].parse().unwrap();
-    let reg_b: i64 = lines[1].split(": ").collect::<Vec<&str>>()[1].parse().unwrap();
-    let reg_c: i64 = lines[2].split(": ").collect::<Vec<&str>>()[1].parse().unwrap();
-    let program = lines[4].split(": ").collect::<Vec<&str>>()[1]
+    let reg_a: i64 = field(&lines[0], "Register A")?;
+    let reg_b: i64 = field(&lines[1], "Register B")?;
+    let reg_c: i64 = field(&lines[2], "Register C")?;
+    let program: Vec<i64> = field::<String>(&lines[4], "Program")?
         .split(',')
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect();
+        .map(|s| lines[4].num(s))
+        .collect::<Result<_, _>>()?;
+    // opcodes and operands are all 3-bit numbers, in pairs
+    if program.iter().any(|n| !(0..8).contains(n)) {
+        return Err(lines[4].error("expected 3-bit numbers"));
+    }
+    if !program.len().is_multiple_of(2) {
+        return Err(lines[4].error("expected an operand after every opcode"));
+    }
+    // adv, bst, out, bdv and cdv take a combo operand, where 7 is reserved
+    if let Some(i) = program.chunks(2).position(|pair| matches!(pair, [0 | 2 | 5 | 6 | 7, 7])) {
+        return Err(lines[4].error(format!("combo operand 7 for instruction {}", i + 1)));
+    }
 
-    Computer::new(reg_a, reg_b, reg_c, program)
+    Ok(Computer::new(reg_a, reg_b, reg_c, program))
 }
 
-pub fn part_1(input: &str) -> String {
-    let mut computer = parse(input);
+// the value of a `<label>: <value>` line
+fn field<T: std::str::FromStr>(line: &Line, label: &str) -> Result<T, ParseError> {
+    match line.split_once(": ")? {
+        (l, value) if l == label => line.num(value),
+        _ => Err(line.error(format!("expected `{}: <value>`", label))),
+    }
+}
+
+pub fn part_1(computer: &Computer) -> Result<String, Error> {
+    let mut computer = computer.clone();
     //println!("{:?}",computer);
-    computer.run();
+    computer.run()?;
     //println!("{:?}",computer);
-    computer.output()
+    Ok(computer.output())
 }
 
 // program opcodes and operands:
@@ -192,36 +249,37 @@ pub fn part_1(input: &str) -> String {
 // starting from the highest bits and iterating on potential values from 0 to 7, in a depth-first
 // manner, the first matching value we find is necessarily the smallest of all potential values.
 
-pub fn part_2(input: &str) -> i64 {
-    let mut computer = parse(input);
+pub fn part_2(computer: &Computer) -> Result<i64, Error> {
     let cursor_max = computer.program.len();
-    find_value(&computer, 1, cursor_max, 0).unwrap()
+    find_value(computer, 1, cursor_max, 0)?
+        .ok_or_else(|| Error::NoAnswer("no value for register A outputs the program".to_string()))
 }
 
 // based on this reddit comment which helped me figure out how to solve this
 // https://www.reddit.com/r/adventofcode/comments/1hg38ah/comment/m2gge90/
-fn find_value(computer: &Computer, curs: usize, cursor_max: usize, reg: i64) -> Option<i64> {
+fn find_value(computer: &Computer, curs: usize, cursor_max: usize, reg: i64) -> Result<Option<i64>, Error> {
     // the potential 3-bit value
     for oct in 0..=7 {
-        // shift our existing value up 3 bits (*8) and add the potential
-        let reg2 = reg * 8 + oct;
+        // shift our existing value up 3 bits (*8) and add the potential. a program too long for
+        // that to fit has no answer
+        let Some(reg2) = reg.checked_mul(8).map(|r| r + oct) else { return Ok(None) };
         let mut c = computer.clone();
         // set register A and run the program
         c.reg_a = reg2;
-        c.run();
+        c.run()?;
         if c.out == computer.program.get((cursor_max-curs)..).unwrap() {
             // if the output matches the last n=curs digits of the program
             if curs == cursor_max {
                 // we've found the 16 3-bit values that recreate the program
-                return Some(reg2)
+                return Ok(Some(reg2))
             } else {
                 // recurse with an incremented cursor and updated register value
-                let v = find_value(computer, curs + 1, cursor_max, reg2);
-                if v != None { return v }
+                let v = find_value(computer, curs + 1, cursor_max, reg2)?;
+                if v.is_some() { return Ok(v) }
             }
         }
     }
-    None
+    Ok(None)
 }
 
 #[cfg(test)]
@@ -230,21 +288,67 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok("4,6,3,5,6,3,5,2,1,0".to_string()));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), "7,6,1,5,3,1,4,2,6");
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok("7,6,1,5,3,1,4,2,6".to_string()));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE2), 117440);
+        assert_eq!(part_2(&parse(inputs::SAMPLE2).unwrap()), Ok(117440));
+    }
+
+    #[test]
+    fn part_2_no_answer() {
+        // the part 1 sample doesn't output itself for any register A
+        assert!(matches!(part_2(&parse(inputs::SAMPLE).unwrap()), Err(Error::NoAnswer(_))));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 164541017976509);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(164541017976509));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        // a computer survives a trip through JSON, output and all
+        let mut computer = parse(inputs::SAMPLE).unwrap();
+        computer.run().unwrap();
+        let json = serde_json::to_string(&computer).unwrap();
+        let back: Computer = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:?}", back), format!("{:?}", computer));
+        assert_eq!(back.out, [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("Register A: 729\nRegister B: x\nRegister C: 0\n\nProgram: 0,1").unwrap_err();
+        assert_eq!(err.to_string(), "day 17, line 2: expected a number, got \"x\": \"Register B: x\"");
+        let err = parse("Register A: 729\nRegister C: 0\nRegister B: 0\n\nProgram: 0,1").unwrap_err();
+        assert_eq!(err.message, "expected `Register B: <value>`");
+        let err = parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (5, "expected 3-bit numbers"));
+        assert_eq!(parse("Register A: 729").unwrap_err().line, 0);
+        let err = parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 2").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (5, "expected an operand after every opcode"));
+        let err = parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,7").unwrap_err();
+        assert_eq!(err.to_string(), "day 17, line 5: combo operand 7 for instruction 2: \"Program: 1,7,5,7\"");
+    }
+
+    #[test]
+    fn run_errors() {
+        // jumping to 1 leaves opcode 1 without an operand
+        let mut computer = Computer::new(1, 0, 0, vec![3, 1]);
+        let err = Error::NoAnswer("the program can't run: no operand for opcode 1 at 1".to_string());
+        assert_eq!(computer.run(), Err(err));
+        let mut computer = Computer::new(1, -1, 0, vec![0, 5]);
+        assert!(matches!(computer.run(), Err(Error::NoAnswer(_))));
+        // dividing by more than fits in an i64 leaves nothing
+        let mut computer = Computer::new(i64::MAX, 70, 0, vec![0, 5, 5, 4]);
+        assert_eq!((computer.run(), computer.output()), (Ok(()), "0".to_string()));
     }
 }
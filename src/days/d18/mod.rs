@@ -1,126 +1,122 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::search::bfs;
+use crate::grid::{Grid, GridLike, Point};
+use crate::solution::{parse_param, parse_positive};
+
+const DAY: u32 = 18;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(18) }
+}
+
+// the size of the memory space and how many bytes have fallen for part 1, from the puzzle text
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Params {
+    pub size: i32,
+    pub bytes: usize,
 }
 
-fn parse(input: &str) -> (Grid<char>, Vec<(i32,i32)>) {
-    let lines = crate::helpers::to_lines(input);
+impl Params {
+    pub const SAMPLE: Self = Self { size: 7, bytes: 12 };
+    pub const REAL: Self = Self { size: 71, bytes: 1024 };
+}
 
+impl crate::solution::Params for Params {
+    fn for_input(name: &str) -> Self {
+        match name {
+            "sample" => Self::SAMPLE,
+            _ => Self::REAL,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "size" => self.size = parse_positive(key, value)?,
+            "bytes" => self.bytes = parse_param(key, value)?,
+            _ => return Err(format!("unknown param {:?} (expected size, bytes)", key)),
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<(i32,i32)>, ParseError> {
     let mut points: Vec<(i32, i32)> = Vec::new();
 
-    for line in lines {
-        let nums: Vec<i32> = line.split(',').map(|s| s.parse::<i32>().unwrap()).collect();
+    for line in crate::helpers::lines(DAY, input) {
+        let (x, y) = line.split_once(",")?;
         points.push(
-            ( *nums.get(0).unwrap(), *nums.get(1).unwrap() )
+            ( line.num(x)?, line.num(y)? )
         );
     }
 
-    let size = if input == inputs::SAMPLE { 7 } else { 71 };
-    let mut grid = Grid::fresh('.', size, size);
-    grid.set('S', 0, 0);
-    grid.set('E', size-1, size-1);
+    Ok(points)
+}
 
-    (grid, points)
+// the empty memory space, with the start and exit in opposite corners
+fn new_grid(size: i32) -> Grid<char> {
+    Grid::fresh('.', size, size)
 }
 
-#[derive(Debug)]
-struct Node {
-    c: char,
-    x: i32,
-    y: i32,
-    cost: i32
+// the first `bytes` to fall, which can't be more than the input has
+fn fallen(points: &[(i32,i32)], bytes: usize) -> Result<&[(i32,i32)], Error> {
+    points.get(..bytes).ok_or_else(|| {
+        Error::Param(format!("bytes is {}, but only {} bytes fall in the input", bytes, points.len()))
+    })
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (mut grid, points) = parse(input);
+// Drop the byte on line `i + 1` of the input. The memory space only has its size once the params
+// are known, so a byte that misses it is found here rather than in `parse`.
+fn drop_byte(grid: &mut Grid<char>, i: usize, (x, y): (i32, i32)) -> Result<(), Error> {
+    if grid.get(x, y).is_none() {
+        let message = format!("outside the {0}x{0} memory space", grid.get_width());
+        return Err(ParseError::new(DAY, i + 1, &format!("{},{}", x, y), message).into());
+    }
+    grid.set('#', x, y);
+    Ok(())
+}
 
-    let num_to_fall = if input == inputs::SAMPLE { 12 } else { 1024 };
+pub fn part_1(points: &[(i32,i32)], params: &Params) -> Result<i32, Error> {
+    let mut grid = new_grid(params.size);
 
-    for i in 0..num_to_fall {
-        let (x,y) = points.get(i).unwrap();
-        grid.set('#', *x, *y);
+    for (i, &p) in fallen(points, params.bytes)?.iter().enumerate() {
+        drop_byte(&mut grid, i, p)?;
     }
 
-    exit_cost(&grid).unwrap()
+    exit_cost(&grid).ok_or_else(|| Error::NoAnswer("the exit is already cut off".to_string()))
 }
 
-pub fn part_2(input: &str) -> String {
-    let (mut grid, points) = parse(input);
+pub fn part_2(points: &[(i32,i32)], params: &Params) -> Result<String, Error> {
+    let mut grid = new_grid(params.size);
 
     // we know from part 1 that there is an exit with these bytes
-    let mut num_to_fall = if input == inputs::SAMPLE { 12 } else { 1024 };
-
     // so go ahead and set them on the grid
-    for i in 0..num_to_fall {
-        let (x,y) = points.get(i).unwrap();
-        grid.set('#', *x, *y);
+    for (i, &p) in fallen(points, params.bytes)?.iter().enumerate() {
+        drop_byte(&mut grid, i, p)?;
     }
 
     // Loop on: drop the next byte, check if we can exit.
     // If not then return the coords of that byte.
-    loop {
-        println!("{:?}", num_to_fall);
-        let (x,y) = points.get(num_to_fall).unwrap();
-        num_to_fall += 1;
-        grid.set('#', *x, *y);
+    for (i, &(x,y)) in points.iter().enumerate().skip(params.bytes) {
+        drop_byte(&mut grid, i, (x, y))?;
         match exit_cost(&grid) {
             Some(_) => continue,
-            None => return format!("{:?},{:?}", x, y)
+            None => return Ok(format!("{:?},{:?}", x, y))
         }
     }
+    Err(Error::NoAnswer("the exit is never cut off".to_string()))
 }
 
-// Pathfind on the grid from S->E, returning the number of steps.
-// Pulled from day 16, implementation of Dijkstra's algorithm, minus the turn cost.
+// Pathfind from the top-left corner to the bottom-right one, returning the number of steps, or none
+// if the exit is cut off. A byte can fall on either corner, which cuts it off too.
 pub fn exit_cost(grid: &Grid<char>) -> Option<i32> {
-    // build a list of unvisited nodes from the grid
-    let mut unvisited: Vec<Node> = Vec::new();
-    for (oc, x, y) in grid.iter() {
-        let c = oc.unwrap();
-        match c {
-            '.' | 'E' => {
-                unvisited.push(Node { c, x, y, cost: i32::MAX });
-            }
-            'S' => {
-                unvisited.push(Node { c, x, y, cost: 0 });
-            }
-            _ => ()
-        }
-    }
-
-    let mut visited: Vec<Node> = Vec::new();
-
-    // sort unvisited to move Start to the front
-    unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-    let mut cur = unvisited.remove(0);
-
-    // loop until we find End
-    loop {
-        for (oc, dx, dy) in grid.neighbors(cur.x, cur.y, false) {
-            let x = cur.x + dx;
-            let y = cur.y + dy;
-            if let Some(i) = unvisited.iter().position(|n| n.x == x && n.y == y) {
-                let cost = cur.cost + 1;
-                let mut node = unvisited.remove(i);
-                if cost < node.cost {
-                    node.cost = cost;
-                }
-                unvisited.push(node);
-            }
-        }
-
-        if unvisited.len() == 0 { return None }
-
-        unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-        cur = unvisited.remove(0);
-
-        // only remaining nodes are unreachable
-        if cur.cost == i32::MAX { return None }
-
-        if cur.c == 'E' { return Some(cur.cost) }
-    }
+    let (start, exit) = (Point::new(0, 0), Point::new(grid.get_width() - 1, grid.get_height() - 1));
+    if grid[start] == '#' { return None }
+    bfs(start, |&p| grid.moves(p, |c| *c != '#'), |&p| p == exit).goal_cost()
 }
 
 #[cfg(test)]
@@ -129,22 +125,69 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 22);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok(22));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 356);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(356));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), "6,1");
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok("6,1".to_string()));
+    }
+
+    #[test]
+    fn bytes_outside() {
+        let points = parse(inputs::SAMPLE).unwrap();
+        let params = Params { size: 5, ..Params::SAMPLE };
+        let err = ParseError::new(DAY, 1, "5,4", "outside the 5x5 memory space");
+        assert_eq!(part_1(&points, &params), Err(Error::Parse(err)));
+        assert!(matches!(part_2(&points, &params), Err(Error::Parse(_))));
+        // the first 12 fit, but a later one doesn't
+        let mut points = points;
+        points.push((7, 0));
+        assert_eq!(part_1(&points, &Params::SAMPLE), Ok(22));
+        let params = Params { bytes: 25, ..Params::SAMPLE };
+        assert_eq!(part_2(&points, &params).unwrap_err().to_string(), "day 18, line 26: outside the 7x7 memory space: \"7,0\"");
+    }
+
+    #[test]
+    fn corners_blocked() {
+        let params = Params { size: 3, bytes: 1 };
+        let err = Error::NoAnswer("the exit is already cut off".to_string());
+        assert_eq!(part_1(&parse("0,0\n1,1").unwrap(), &params), Err(err));
+        assert_eq!(part_2(&parse("1,1\n2,2").unwrap(), &params), Ok("2,2".to_string()));
+        assert_eq!(part_1(&parse("1,1").unwrap(), &params), Ok(4));
+    }
+
+    #[test]
+    fn params() {
+        use crate::solution::Params as _;
+        let mut params = Params::SAMPLE;
+        assert!(params.set("size", "0").is_err());
+        assert_eq!(params.set("size", "9"), Ok(()));
+        assert_eq!(params.size, 9);
+    }
+
+    #[test]
+    fn too_many_bytes() {
+        let points = parse(inputs::SAMPLE).unwrap();
+        let params = Params { bytes: 26, ..Params::SAMPLE };
+        let err = Error::Param("bytes is 26, but only 25 bytes fall in the input".to_string());
+        assert_eq!(part_1(&points, &params), Err(err));
+        assert!(matches!(part_2(&points, &params), Err(Error::Param(_))));
+        // all of them can fall, but then there are none left to cut off the exit
+        let params = Params { bytes: 25, ..Params::SAMPLE };
+        assert_eq!(part_2(&points, &params), Err(Error::NoAnswer("the exit is never cut off".to_string())));
     }
 
+    // a couple of seconds on a release build but half a minute on a debug one, so it only runs with
+    // `cargo test --release -- --include-ignored`
     #[test]
+    #[ignore = "slow on a debug build"]
     fn part_2_real() {
-        // takes a minute
-        //assert_eq!(part_2(inputs::REAL), "22,33");
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok("22,33".to_string()));
     }
 }
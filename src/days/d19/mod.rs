@@ -1,28 +1,42 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 19;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(19) }
 }
 
-fn parse(input: &str) -> (Vec<String>, Vec<String>) {
-    let mut lines = crate::helpers::to_lines(input);
-    let patterns: Vec<String> = lines.remove(0).split(", ").map(|s| s.to_string()).collect();
+pub fn parse(input: &str) -> Result<(Vec<String>, Vec<String>), ParseError> {
+    let mut lines = crate::helpers::lines(DAY, input);
+    let first = lines.next().ok_or(ParseError::new(DAY, 0, "", "empty input"))?;
+    let patterns: Vec<String> = first.text.split(", ").map(|s| s.to_string()).collect();
 
-    lines.remove(0); // remove empty line. remaining `lines` is the designs
+    // skip the empty line. remaining `lines` are the designs
+    if let Some(line) = lines.next() {
+        if !line.text.is_empty() {
+            return Err(line.error("expected an empty line after the patterns"));
+        }
+    }
 
-    (patterns, lines)
+    Ok((patterns, lines.map(|l| l.text.to_string()).collect()))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (patterns, designs) = parse(input);
+pub fn part_1(parsed: &(Vec<String>, Vec<String>)) -> Result<i32, Error> {
+    let (patterns, designs) = parsed;
     let mut possible = 0;
     for design in designs {
-        if possible_design(&design, 0, &patterns) {
+        if possible_design(design, 0, patterns) {
             possible += 1;
         }
     }
-    possible
+    Ok(possible)
 }
 
 // Move through slices of the design, checking if any pattern matches.
@@ -51,25 +65,25 @@ fn possible_design(design: &str, cursor: i32, patterns: &Vec<String>) -> bool {
 // This alone will take a long time because there are many variants.
 // By memoizing with a HashMap on design/cursor => count, we
 // greatly speed up the solution.
-pub fn part_2(input: &str) -> i64 {
-    let (patterns, designs) = parse(input);
+pub fn part_2(parsed: &(Vec<String>, Vec<String>)) -> Result<i64, Error> {
+    let (patterns, designs) = parsed;
     let mut variants = 0;
 
     let mut cache: HashMap::<String, i64> = HashMap::new();
 
     for design in designs {
         let mut possible_pats: Vec<String> = Vec::new();
-        for pattern in &patterns {
-            if design.contains(&*pattern) {
+        for pattern in patterns {
+            if design.contains(pattern) {
                 possible_pats.push(pattern.clone());
             }
         }
-        let (design_vars, c) = num_variants(&design, 0, &possible_pats, cache);
+        let (design_vars, c) = num_variants(design, 0, &possible_pats, cache);
         cache = c;
         variants += design_vars;
     }
 
-    variants
+    Ok(variants)
 }
 
 fn num_variants(design: &str, cursor: i64, patterns: &Vec<String>, mut cache: HashMap<String, i64>) -> (i64, HashMap<String, i64>) {
@@ -105,21 +119,21 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 6);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(6));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 220);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(220));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 16);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(16));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 565600047715343);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(565600047715343));
     }
 }
@@ -1,55 +1,94 @@
-use crate::grid::Grid;
+use crate::grid::search::bfs;
+use crate::grid::{Grid, GridLike, Point};
 use std::collections::HashMap;
+use crate::solution::parse_param;
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 20;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(20) }
+}
+
+// the puzzle only counts cheats that save at least this many picoseconds
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Params {
+    pub min_saving: i32,
 }
 
-fn parse(input: &str) -> Grid<char> {
-    Grid::from_lines(crate::helpers::to_lines(input))
+impl Params {
+    // the sample's cheats are all small, so use one of the thresholds from the puzzle text
+    pub const SAMPLE: Self = Self { min_saving: 50 };
+    pub const REAL: Self = Self { min_saving: 100 };
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let mut grid = parse(input);
-    let path = pathfind(&grid);
+impl crate::solution::Params for Params {
+    fn for_input(name: &str) -> Self {
+        match name {
+            "sample" => Self::SAMPLE,
+            _ => Self::REAL,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "min_saving" => self.min_saving = parse_param(key, value)?,
+            _ => return Err(format!("unknown param {:?} (expected min_saving)", key)),
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let grid = Grid::parse_chars(DAY, input, "'#', '.', 'S' or 'E'", |c| "#.SE".contains(c))?;
+    for c in ['S', 'E'] {
+        crate::helpers::one_marker(DAY, input, c, &format!("{:?} on the racetrack", c))?;
+    }
+    Ok(grid)
+}
+
+pub fn part_1(grid: &Grid<char>, params: &Params) -> Result<i32, Error> {
+    let path = pathfind(grid)?;
 
     let cheat_counts = count_cheats(path, 2);
 
-    let mut gteq_100 = 0;
+    let mut good_cheats = 0;
     for (key, val) in cheat_counts.iter() {
-        if *key >= 100 { gteq_100 += val; }
+        if *key >= params.min_saving { good_cheats += val; }
     }
-    gteq_100
+    Ok(good_cheats)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let mut grid = parse(input);
-    let path = pathfind(&grid);
+pub fn part_2(grid: &Grid<char>, params: &Params) -> Result<i32, Error> {
+    let path = pathfind(grid)?;
 
     let cheat_counts = count_cheats(path, 20);
 
-    let mut gteq_100 = 0;
+    let mut good_cheats = 0;
     for (key, val) in cheat_counts.iter() {
-        if *key >= 100 { gteq_100 += val; }
+        if *key >= params.min_saving { good_cheats += val; }
     }
-    gteq_100
+    Ok(good_cheats)
 }
 
 // Count the possible cheats on the path, returning a map of time saved => number of cheats
-fn count_cheats(path: Vec<(i32, i32)>, cheat_len: i32) -> HashMap<i32, i32> {
+fn count_cheats(path: Vec<Point>, cheat_len: i32) -> HashMap<i32, i32> {
     let mut cheat_counts: HashMap<i32, i32> = HashMap::new();
 
     for i in 0..path.len() {
-        for j in 0..path.len() {
-            if j == i { continue } // same
-            if j > i { continue } // already checked when i > j
+        // only the points before `i`, since the ones after pair with it when it's their turn
+        for j in 0..i {
             let dist = (i - j) as i32; // num steps between the points
             if dist <= cheat_len { continue } // not worth cheating
             let m = path.get(i).unwrap();
             let n = path.get(j).unwrap();
             // taxicab distance between points, i.e. the time to cheat
-            let cheat_dist = (m.0 - n.0).abs() + (m.1 - n.1).abs();
+            let cheat_dist = m.manhattan(*n);
             if (cheat_dist <= cheat_len) {
                 let time_saved = dist - cheat_dist;
                 let count = cheat_counts.entry(time_saved).or_insert(0);
@@ -61,104 +100,48 @@ fn count_cheats(path: Vec<(i32, i32)>, cheat_len: i32) -> HashMap<i32, i32> {
     cheat_counts
 }
 
-#[derive(Clone,Debug)]
-struct Node {
-    c: char,
-    x: i32,
-    y: i32,
-    cost: i32,
-    prev: Option<(i32, i32)>
-}
-
-// Dijkstra's algorithm to find the path on the track
-fn pathfind(grid: &Grid<char>) -> Vec<(i32, i32)> {
-    // build a list of unvisited nodes from the grid
-    let mut unvisited: Vec<Node> = Vec::new();
-    for (oc, x, y) in grid.iter() {
-        let c = oc.unwrap();
-        match c {
-            '.' | 'E' => {
-                unvisited.push(Node {
-                    c, x, y,
-                    cost: i32::MAX,
-                    prev: None
-                });
-            }
-            'S' => {
-                unvisited.push(Node {
-                    c, x, y,
-                    cost: 0,
-                    prev: None
-                });
-            }
-            _ => ()
-        }
-    }
-
-    let mut visited: Vec<Node> = Vec::new();
-
-    // sort unvisited to move Start to the front
-    unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-    let mut cur = unvisited.remove(0);
-
-    // loop until we find End
-    loop {
-        for (oc, dx, dy) in grid.neighbors(cur.x, cur.y, false) {
-            let x = cur.x + dx;
-            let y = cur.y + dy;
-            if let Some(i) = unvisited.iter().position(|n| n.x == x && n.y == y) {
-                let node = unvisited.get_mut(i).unwrap();
-                let cost = cur.cost + 1;
-                if cost < node.cost {
-                    node.cost = cost;
-                    node.prev = Some((cur.x,cur.y));
-                }
-            }
-        }
-
-        visited.push(cur);
-
-        unvisited.sort_by(|m, n| m.cost.cmp(&n.cost));
-        cur = unvisited.remove(0); // swap_remove?
-
-        if cur.c == 'E' {
-            // unwind the nodes' prev values to get the best path
-            let mut path = vec![(cur.x,cur.y)];
-            let mut node = cur.clone();
-            let mut coords = cur.prev;
-            while coords != None {
-                let co = coords.unwrap();
-                path.push(coords.unwrap());
-                node = visited.iter().find(|node| node.x == co.0 && node.y == co.1).unwrap().clone();
-                coords = node.prev;
-            }
-            return path;
-        }
-        if unvisited.len() == 0 { panic!("visited all nodes!") }
-    }
+// there's only one path on the track, from S to E
+fn pathfind(grid: &Grid<char>) -> Result<Vec<Point>, Error> {
+    let start = grid.find_point('S').unwrap();
+    let search = bfs(start, |&p| grid.moves(p, |c| *c != '#'), |&p| grid[p] == 'E');
+    search.goal_path().ok_or_else(|| Error::NoAnswer("no path from S to E".to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /*#[test]
+    #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 0);
-    }*/
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok(1));
+    }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 1321);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(1321));
     }
 
-    /*#[test]
+    #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 0);
-    }*/
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok(285));
+    }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 971737);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(971737));
+    }
+
+    #[test]
+    fn no_path() {
+        let grid = parse("S#E").unwrap();
+        assert_eq!(part_1(&grid, &Params::SAMPLE), Err(Error::NoAnswer("no path from S to E".to_string())));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("#S.#\n#.E.x").unwrap_err();
+        assert_eq!(err.to_string(), "day 20, line 2: column 5: unexpected 'x' (expected '#', '.', 'S' or 'E'): \"#.E.x\"");
+        assert_eq!(parse("#..E").unwrap_err().to_string(), "day 20: no 'S' on the racetrack");
+        assert_eq!(parse("SS.E").unwrap_err().message, "more than one 'S' on the racetrack");
     }
 }
@@ -1,14 +1,27 @@
-use crate::grid::Grid;
+use crate::error::{Error, ParseError};
+use crate::grid::{Dir4, Grid};
 use std::collections::HashMap;
 use std::{thread, time};
 
+const DAY: u32 = 21;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(21) }
 }
 
-fn parse(input: &str) -> Vec<String> {
-    crate::helpers::to_lines(input)
+// door codes are 3 digits and then `A`, e.g. `029A`
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    crate::helpers::lines(DAY, input)
+        .map(|l| match l.text.strip_suffix('A') {
+            Some(num) if num.len() == 3 && num.chars().all(|c| c.is_ascii_digit()) => Ok(l.text.to_string()),
+            _ => Err(l.error("expected a door code like `029A`")),
+        })
+        .collect()
 }
 
 /*
@@ -36,8 +49,7 @@ pub fn part_1_original(input: &str) -> i32 {
 }
 */
 
-pub fn part_1(input: &str) -> i64 {
-    let codes = parse(input);
+pub fn part_1(codes: &[String]) -> Result<i64, Error> {
     let mut robot = Robot::new(4);
     let mut total_complexity = 0;
 
@@ -51,11 +63,10 @@ pub fn part_1(input: &str) -> i64 {
         total_complexity += complexity;
     }
 
-    total_complexity
+    Ok(total_complexity)
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let codes = parse(input);
+pub fn part_2(codes: &[String]) -> Result<i64, Error> {
     let mut robot = Robot::new(27);
     let mut total_complexity = 0;
 
@@ -69,7 +80,7 @@ pub fn part_2(input: &str) -> i64 {
         total_complexity += complexity;
     }
 
-    total_complexity
+    Ok(total_complexity)
 }
 
 /*
@@ -79,16 +90,7 @@ fn print_keys(keys: &Vec<char>) {
 }
 */
 
-fn dir_to_char(dir: Dir) -> char {
-    match dir {
-        Dir::U => '^',
-        Dir::R => '>',
-        Dir::D => 'v',
-        Dir::L => '<'
-    }
-}
-
-struct Robot {
+pub struct Robot {
     id: i32,
     pad: Grid<char>,
     cur: char,
@@ -160,7 +162,7 @@ impl Robot {
         let dirs = get_dirs(&self.pad, self.cur, c);
         self.cur = c;
         // chars that controller needs to press to move self
-        let mut chars: Vec<char> = dirs.into_iter().map(|d| dir_to_char(d)).collect();
+        let mut chars: Vec<char> = dirs.into_iter().map(|d| d.arrow()).collect();
         // controller needs to press A afterwards
         chars.push('A');
         match &mut self.controller {
@@ -189,7 +191,7 @@ impl Robot {
         let dirs = get_dirs(&self.pad, self.cur, c);
         self.cur = c;
         // chars that controller needs to press to move self
-        let mut chars: Vec<char> = dirs.into_iter().map(|d| dir_to_char(d)).collect();
+        let mut chars: Vec<char> = dirs.into_iter().map(|d| d.arrow()).collect();
         // controller needs to press A afterwards
         chars.push('A');
         let num = match &mut self.controller {
@@ -204,7 +206,7 @@ impl Robot {
             }
         };
 
-        self.presses_cache.insert(cache_key.clone(), num.into());
+        self.presses_cache.insert(cache_key.clone(), num);
 
         num
     }
@@ -220,8 +222,8 @@ struct Node {
 }
 
 // get a list of the directions to move from key `start` to key `end` on keypad `grid`
-fn get_dirs(grid: &Grid<char>, start: char, end: char) -> Vec<Dir> {
-    let mut dirs = Vec::<Dir>::new();
+fn get_dirs(grid: &Grid<char>, start: char, end: char) -> Vec<Dir4> {
+    let mut dirs = Vec::<Dir4>::new();
 
     if start == end { return dirs }
 
@@ -250,10 +252,10 @@ fn get_dirs(grid: &Grid<char>, start: char, end: char) -> Vec<Dir> {
     let c = grid.get(x1, y1+dy).unwrap();
     if c == '#' {
         done = true;
-        for _ in 0..left { dirs.push(Dir::L) }
-        for _ in 0..right { dirs.push(Dir::R) }
-        for _ in 0..up { dirs.push(Dir::U) }
-        for _ in 0..down { dirs.push(Dir::D) }
+        for _ in 0..left { dirs.push(Dir4::L) }
+        for _ in 0..right { dirs.push(Dir4::R) }
+        for _ in 0..up { dirs.push(Dir4::U) }
+        for _ in 0..down { dirs.push(Dir4::D) }
     }
 
     // if L/R is the empty spot, go U/D first
@@ -261,10 +263,10 @@ fn get_dirs(grid: &Grid<char>, start: char, end: char) -> Vec<Dir> {
         let c = grid.get(x1+dx, y1).unwrap();
         if c == '#' {
             done = true;
-            for _ in 0..up { dirs.push(Dir::U) }
-            for _ in 0..down { dirs.push(Dir::D) }
-            for _ in 0..left { dirs.push(Dir::L) }
-            for _ in 0..right { dirs.push(Dir::R) }
+            for _ in 0..up { dirs.push(Dir4::U) }
+            for _ in 0..down { dirs.push(Dir4::D) }
+            for _ in 0..left { dirs.push(Dir4::L) }
+            for _ in 0..right { dirs.push(Dir4::R) }
         }
     }
 
@@ -278,49 +280,36 @@ fn get_dirs(grid: &Grid<char>, start: char, end: char) -> Vec<Dir> {
         // This is hard to get an intuitive sense of, but you can prove it by thinking
         // about the moves required in a chain length greater than 3.
         // https://www.reddit.com/r/adventofcode/comments/1hj7f89/comment/m34erhg
-        for _ in 0..left { dirs.push(Dir::L) }
-        for _ in 0..up { dirs.push(Dir::U) }
-        for _ in 0..down { dirs.push(Dir::D) }
-        for _ in 0..right { dirs.push(Dir::R) }
+        for _ in 0..left { dirs.push(Dir4::L) }
+        for _ in 0..up { dirs.push(Dir4::U) }
+        for _ in 0..down { dirs.push(Dir4::D) }
+        for _ in 0..right { dirs.push(Dir4::R) }
     }
 
     dirs
 }
 
-#[derive(Debug)]
-pub enum Dir { U, D, L, R }
-
-fn offset_to_dir(dx: i32, dy: i32) -> Dir {
-    match (dx,dy) {
-        (0, -1) => Dir::U,
-        (1, 0) => Dir::R,
-        (0, 1) => Dir::D,
-        (-1, 0) => Dir::L,
-        _ => panic!("unexpected offsets {:?},{:?}", dx, dy)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 126384);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(126384));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 184180);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(184180));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 154115708116294);
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Ok(154115708116294));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 231309103124520);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(231309103124520));
     }
 }
@@ -1,36 +1,42 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 22;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE), ("sample2", SAMPLE2)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(22) }
 }
 
-fn parse(input: &str) -> Vec<i64> {
-    crate::helpers::to_lines(input).iter().map(|s| s.parse::<i64>().unwrap()).collect()
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    crate::helpers::lines(DAY, input).map(|l| l.num(l.text)).collect()
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let starting_secret = parse(input);
+pub fn part_1(starting_secret: &[i64]) -> Result<i64, Error> {
     let mut sum = 0;
-    for starting_secret in starting_secret {
+    for &starting_secret in starting_secret {
         let mut secret = starting_secret;
         for _ in 0..2000 {
             secret = evolve(secret);
         }
         sum += secret;
     }
-    sum
+    Ok(sum)
 }
 
 type Diffs = HashMap::<[i64; 4], i64>;
 
-pub fn part_2(input: &str) -> i64 {
-    let starting_secrets = parse(input);
+pub fn part_2(starting_secrets: &[i64]) -> Result<i64, Error> {
     let mut caches: Vec<Diffs> = Vec::new();
 
     // For each secret, find the diffs and first price at each 4-diff sequence
-    for starting_secret in starting_secrets {
+    for &starting_secret in starting_secrets {
         let mut cache: Diffs = HashMap::new();
         let mut prices: Vec<i64> = Vec::new();
         let mut diffs: Vec<i64> = Vec::new();
@@ -61,7 +67,7 @@ pub fn part_2(input: &str) -> i64 {
     let mut diffs: Vec<[i64; 4]> = Vec::new();
     for cache in &caches {
         for (k,v) in cache {
-            if (diffs.contains(&k)) { continue }
+            if (diffs.contains(k)) { continue }
             diffs.push(*k);
         }
     }
@@ -78,7 +84,7 @@ pub fn part_2(input: &str) -> i64 {
         if bananas > best { best = bananas }
     }
 
-    best
+    Ok(best)
 }
 
 fn evolve(mut secret: i64) -> i64 {
@@ -102,22 +108,23 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 37327623);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(37327623));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 15303617151);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(15303617151));
     }
 
     #[test]
     fn part_2_sample2() {
-        assert_eq!(part_2(inputs::SAMPLE2), 23);
+        assert_eq!(part_2(&parse(inputs::SAMPLE2).unwrap()), Ok(23));
     }
 
     #[test]
+    #[ignore = "slow; run with --release --ignored"]
     fn part_2_real() {
         // takes a minute on a release build
-        //assert_eq!(part_2(inputs::REAL), 1727);
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(1727));
     }
 }
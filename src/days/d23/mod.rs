@@ -1,29 +1,62 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use itertools::Itertools;
+use crate::solution::parse_param;
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 23;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(23) }
+}
+
+// Part 2 only looks for sets of at least `min_size` computers. The answer for the sample is given,
+// and for the real input we can work down from a guess until we get the solution.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Params {
+    pub min_size: usize,
+}
+
+impl Params {
+    pub const SAMPLE: Self = Self { min_size: 4 };
+    pub const REAL: Self = Self { min_size: 13 };
+}
+
+impl crate::solution::Params for Params {
+    fn for_input(name: &str) -> Self {
+        match name {
+            "sample" => Self::SAMPLE,
+            _ => Self::REAL,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "min_size" => self.min_size = parse_param(key, value)?,
+            _ => return Err(format!("unknown param {:?} (expected min_size)", key)),
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
-struct Computer {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Computer {
     name: String,
     links: Vec<String>
 }
 
 // build a list of all the computers and their links
-fn parse(input: &str) -> HashMap<String, Computer> {
-    let lines = crate::helpers::to_lines(input);
-
+pub fn parse(input: &str) -> Result<HashMap<String, Computer>, ParseError> {
     let mut links: Vec<(String, String)> = Vec::new();
-    for line in lines {
-        let computers: Vec<&str> = line.split("-").collect();
-        links.push((
-                computers.get(0).unwrap().to_string(),
-                computers.get(1).unwrap().to_string(),
-        ));
+    for line in crate::helpers::lines(DAY, input) {
+        let (c, d) = line.split_once("-")?;
+        links.push((c.to_string(), d.to_string()));
     }
 
     let mut computers: HashMap<String, Computer> = HashMap::new();
@@ -57,19 +90,18 @@ fn parse(input: &str) -> HashMap<String, Computer> {
         }
     }
 
-    computers
+    Ok(computers)
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let computers = parse(input);
+pub fn part_1(computers: &HashMap<String, Computer>, _params: &Params) -> Result<i32, Error> {
 
     let mut tri_links: Vec<String> = Vec::new();
 
     // for each computer
-    for (m, c) in &computers {
+    for (m, c) in computers {
         // for each pair of links
         for two_links in c.links.iter().combinations(2) {
-            let n = two_links.get(0).unwrap();
+            let n = two_links.first().unwrap();
             let o = two_links.get(1).unwrap();
 
             // there is a rule that one of their names must start with "t"
@@ -82,7 +114,7 @@ pub fn part_1(input: &str) -> i32 {
 
             // if the pair connect to each other, add them to our list of tri_links
             if d.links.contains(o) && e.links.contains(n) {
-                let mut names = vec![m,n,o];
+                let mut names = [m,n,o];
                 names.sort();
                 let joined = names.iter().join(",");
                 if !tri_links.contains(&joined) {
@@ -92,19 +124,18 @@ pub fn part_1(input: &str) -> i32 {
         }
     }
 
-    tri_links.len() as i32
+    Ok(tri_links.len() as i32)
 }
 
-pub fn part_2(input: &str) -> String {
-    let computers = parse(input);
+pub fn part_2(computers: &HashMap<String, Computer>, params: &Params) -> Result<String, Error> {
 
     // cache for
     // "do the computers with these names all connect to every other computer in the set"
     let mut connected: HashMap<String, bool> = HashMap::new();
 
-    let min_size = if input == inputs::REAL { 13 } else { 4 };
+    let min_size = params.min_size;
 
-    for (m, c) in &computers {
+    for (m, c) in computers {
         // build a list of all names, self + links
         let mut all = c.links.clone();
         all.push(m.clone());
@@ -113,8 +144,7 @@ pub fn part_2(input: &str) -> String {
         // We know from the input that each computer is connected to at least one other (2),
         // i.e. there are some links.
         //for i in 2..all.len() {
-        // Optimize by using a min_size > 2. The answer for the sample is given,
-        // and for the real input we can work down from a guess until we get the solution.
+        // Optimize by using a min_size > 2 (see `Params`).
         for i in min_size..all.len() {
             for mut names in all.iter().combinations(i) {
                 // Sort the names for the cache key so the cache is optimal,
@@ -125,7 +155,7 @@ pub fn part_2(input: &str) -> String {
                 match connected.entry(key) {
                     Entry::Occupied(o) => { continue }
                     Entry::Vacant(v) => {
-                        v.insert(all_connected(names, &computers));
+                        v.insert(all_connected(names, computers));
                     }
                 }
             }
@@ -141,7 +171,10 @@ pub fn part_2(input: &str) -> String {
         }
     }
 
-    largest
+    if largest.is_empty() {
+        return Err(Error::NoAnswer(format!("no set of {} or more connected computers", min_size)));
+    }
+    Ok(largest)
 }
 
 fn all_connected(mut names: Vec<&String>, computers: &HashMap<String, Computer>) -> bool {
@@ -149,7 +182,7 @@ fn all_connected(mut names: Vec<&String>, computers: &HashMap<String, Computer>)
         let computer = computers.get(*n).unwrap();
         for m in &names {
             if n == m { continue }
-            if !computer.links.contains(&m) {
+            if !computer.links.contains(m) {
                 return false;
             }
         }
@@ -163,21 +196,37 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 7);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok(7));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 1046);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok(1046));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), "co,de,ka,ta");
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap(), &Params::SAMPLE), Ok("co,de,ka,ta".to_string()));
+    }
+
+    #[test]
+    fn part_2_too_big() {
+        let params = Params { min_size: 5 };
+        let err = Error::NoAnswer("no set of 5 or more connected computers".to_string());
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap(), &params), Err(err));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), "de,id,ke,ls,po,sn,tf,tl,tm,uj,un,xw,yz");
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap(), &Params::REAL), Ok("de,id,ke,ls,po,sn,tf,tl,tm,uj,un,xw,yz".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let computers = parse(inputs::SAMPLE).unwrap();
+        let json = serde_json::to_string(&computers).unwrap();
+        let back: HashMap<String, Computer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(part_2(&back, &Params::SAMPLE), Ok("co,de,ka,ta".to_string()));
     }
 }
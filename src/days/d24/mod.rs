@@ -1,47 +1,60 @@
-use std::str::FromStr;
 use std::collections::HashMap;
 use regex::Regex;
 
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 24;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
     pub const SAMPLE2: &str = include_str!("sample2");
     pub const SAMPLE3: &str = include_str!("sample3");
+
+    pub const ALL: &[(&str, &str)] = &[
+        ("sample", SAMPLE),
+        ("sample2", SAMPLE2),
+        ("sample3", SAMPLE3),
+    ];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(24) }
 }
 
 #[derive(Clone,Debug)]
-struct Gate {
-    name: String,
-    value: i32,
-    ready: bool,
-    input1: Option<String>,
-    input2: Option<String>,
-    operator: Op
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gate {
+    pub name: String,
+    pub value: i32,
+    pub ready: bool,
+    pub input1: Option<String>,
+    pub input2: Option<String>,
+    pub operator: Op
 }
 
 #[derive(Clone,Debug,PartialEq)]
-enum Op { And, Or, Xor, None }
-
-type Gates = HashMap<String, Gate>;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Op { And, Or, Xor, None }
 
-fn parse(input: &str) -> Gates {
-    let lines = crate::helpers::to_lines(input);
+pub type Gates = HashMap<String, Gate>;
 
+pub fn parse(input: &str) -> Result<Gates, ParseError> {
     let mut gates: Gates = HashMap::new();
 
-    let start_re = Regex::new(r"(.+): (\d)").unwrap();
-    let conns_re = Regex::new(r"(.+) (AND|OR|XOR) (.+) -> (.+)").unwrap();
+    let start_re = Regex::new(r"^(.+): ([01])$").unwrap();
+    let conns_re = Regex::new(r"^(.+) (AND|OR|XOR) (.+) -> (.+)$").unwrap();
 
     let mut done_start = false;
+    // the gate lines and their inputs, checked once every wire is known
+    let mut inputs = Vec::new();
 
-    for line in &lines {
+    for line in crate::helpers::lines(DAY, input) {
         if !done_start {
-            if line == "" {
+            if line.text.is_empty() {
                 done_start = true;
             } else {
-                let caps = start_re.captures(&line).unwrap();
-                let name = caps.get(1).unwrap().as_str().to_string();
-                let value: i32 = FromStr::from_str(caps.get(2).unwrap().as_str()).unwrap();
+                let caps = line.captures(&start_re, "<wire>: <0 or 1>")?;
+                let name = caps[1].to_string();
+                let value: i32 = line.num(&caps[2])?;
                 let gate = Gate {
                     name: name.clone(),
                     value,
@@ -55,9 +68,9 @@ fn parse(input: &str) -> Gates {
             continue;
         }
 
-        let caps = conns_re.captures(&line).unwrap();
-        let input1 = caps.get(1).unwrap().as_str().to_string();
-        let operator_str = caps.get(2).unwrap().as_str();
+        let caps = line.captures(&conns_re, "<wire> <AND|OR|XOR> <wire> -> <wire>")?;
+        let input1 = caps[1].to_string();
+        let operator_str = &caps[2];
         let operator: Op = match operator_str {
             "AND" => Op::And,
             "OR" => Op::Or,
@@ -65,33 +78,41 @@ fn parse(input: &str) -> Gates {
             _ => panic!("unexpected operator {}", operator_str)
         };
 
-        let input2 = caps.get(3).unwrap().as_str().to_string();
-        let name = caps.get(4).unwrap().as_str().to_string();
+        let input2 = caps[3].to_string();
+        let name = caps[4].to_string();
         let gate = Gate {
             name: name.clone(),
             value: 0,
             ready: false,
-            input1: Some(input1),
-            input2: Some(input2),
+            input1: Some(input1.clone()),
+            input2: Some(input2.clone()),
             operator
         };
         gates.insert( name, gate );
+        inputs.push((line, [input1, input2]));
+    }
+
+    // every input has to be a starting wire or some gate's output
+    for (line, wires) in inputs {
+        if let Some(wire) = wires.iter().find(|w| !gates.contains_key(*w)) {
+            return Err(line.error(format!("no wire {:?} to take as input", wire)));
+        }
     }
 
-    gates
+    Ok(gates)
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let mut gates = parse(input);
-    run(&mut gates);
-    get_z(&gates)
+pub fn part_1(gates: &Gates) -> Result<i64, Error> {
+    let mut gates = gates.clone();
+    run(&mut gates)?;
+    Ok(get_z(&gates))
 }
 
 // Identified swapped wires by manually inspecting the input's wiring.
 // dkr,ggk,hhh,htp,rhv,z05,z15,z20
-// The function body does the swapping and asserts that it works.
-pub fn part_2(input: &str) {
-    let mut gates = parse(input);
+// The function body does the swapping and checks that it works, so any other input has no answer.
+pub fn part_2(gates: &Gates) -> Result<(), Error> {
+    let mut gates = gates.clone();
     // y05 AND x05 -> z05    carry - WRONG - should be -> dkr
     // gcs XOR hdc -> dkr    sum out - WRONG - should be -> z05
     // bhw XOR sth -> htp    sum out - WRONG - should be -> z15
@@ -106,13 +127,17 @@ pub fn part_2(input: &str) {
     let sum = get_x(&gates) + get_y(&gates);
 
     // run mis-wired adder
-    run(&mut gates);
-    assert_ne!(get_z(&gates), sum);
+    run(&mut gates)?;
+    if get_z(&gates) == sum {
+        return Err(Error::NoAnswer("the adder already adds up, so no wires need swapping".to_string()));
+    }
 
     // swap the wires
     for (m,n) in swaps {
-        let mut a = gates.get(m).unwrap().clone();
-        let mut b = gates.get(n).unwrap().clone();
+        let (Some(a), Some(b)) = (gates.get(m), gates.get(n)) else {
+            return Err(Error::NoAnswer(format!("no {} and {} wires to swap", m, n)));
+        };
+        let (mut a, mut b) = (a.clone(), b.clone());
 
         a.name = n.to_string();
         gates.insert(n.to_string(), a);
@@ -123,11 +148,16 @@ pub fn part_2(input: &str) {
 
     // run properly wired adder
     reset(&mut gates);
-    run(&mut gates);
-    assert_eq!(get_z(&gates), sum);
+    run(&mut gates)?;
+    if get_z(&gates) != sum {
+        return Err(Error::NoAnswer("swapping the wires doesn't fix the adder".to_string()));
+    }
+    Ok(())
 }
 
-fn run(mut gates: &mut Gates) {
+// Run the gates until every one has an output. A pass that readies no more gates means the
+// rest are wired in a loop and never will.
+pub fn run(mut gates: &mut Gates) -> Result<(), Error> {
     let mut names: Vec<String> = Vec::new();
     for (name, gate) in &mut *gates {
         names.push(name.clone());
@@ -135,6 +165,7 @@ fn run(mut gates: &mut Gates) {
     // not sure why this uses a borrow making get_mut not work
     //let names = gates.keys().clone();
 
+    let mut last_todo = usize::MAX;
     loop {
         let mut todo = 0;
         for name in &names {
@@ -147,8 +178,8 @@ fn run(mut gates: &mut Gates) {
                     continue;
                 }
 
-                let input1val = input1.value.clone();
-                let input2val = input2.value.clone();
+                let input1val = input1.value;
+                let input2val = input2.value;
 
                 let val = match gate.operator {
                     Op::And => input1val & input2val,
@@ -164,18 +195,23 @@ fn run(mut gates: &mut Gates) {
         }
         //println!("todo: {:?}", todo);
         if todo == 0 { break }
+        if todo == last_todo {
+            return Err(Error::NoAnswer(format!("the wiring loops, so {} gates never get both inputs", todo)));
+        }
+        last_todo = todo;
     }
+    Ok(())
 }
 
 // get the Nth bit (from right) in the binary rep of num
 fn get_bit(num: i64, place: i32) -> i32 {
     // ( num % (2 ** p+1) ) / (2 ** p)
-    let numer: i64 = (num % 2_i64.pow((place+1) as u32)).try_into().unwrap();
-    let denom: i64 = 2_i64.pow(place as u32).try_into().unwrap();
+    let numer: i64 = num % 2_i64.pow((place+1) as u32);
+    let denom: i64 = 2_i64.pow(place as u32);
     (numer / denom).try_into().unwrap()
 }
 
-fn set_x(mut gates: &mut Gates, num: i64) {
+pub fn set_x(mut gates: &mut Gates, num: i64) {
     for (name, gate) in gates {
         if name.starts_with("x") {
             //println!("setting {}", name);
@@ -188,7 +224,7 @@ fn set_x(mut gates: &mut Gates, num: i64) {
     }
 }
 
-fn set_y(mut gates: &mut Gates, num: i64) {
+pub fn set_y(mut gates: &mut Gates, num: i64) {
     for (name, gate) in gates {
         if name.starts_with("y") {
             let id = name.get(1..).unwrap().parse::<i32>().unwrap();
@@ -197,7 +233,7 @@ fn set_y(mut gates: &mut Gates, num: i64) {
     }
 }
 
-fn get_x(gates: &Gates) -> i64 {
+pub fn get_x(gates: &Gates) -> i64 {
     let mut num = 0_i64;
     for (name, gate) in gates {
         if name.starts_with("x") {
@@ -211,7 +247,7 @@ fn get_x(gates: &Gates) -> i64 {
     num
 }
 
-fn get_y(gates: &Gates) -> i64 {
+pub fn get_y(gates: &Gates) -> i64 {
     let mut num = 0_i64;
     for (name, gate) in gates {
         if name.starts_with("y") {
@@ -225,7 +261,7 @@ fn get_y(gates: &Gates) -> i64 {
     num
 }
 
-fn get_z(gates: &Gates) -> i64 {
+pub fn get_z(gates: &Gates) -> i64 {
     let mut num = 0_i64;
     for (name, gate) in gates {
         if name.starts_with("z") {
@@ -240,7 +276,7 @@ fn get_z(gates: &Gates) -> i64 {
 }
 
 // reset the ready flags
-fn reset(mut gates: &mut Gates) {
+pub fn reset(mut gates: &mut Gates) {
     for (name, gate) in gates {
         gate.ready = (gate.operator == Op::None);
     }
@@ -356,28 +392,45 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 4);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(4));
     }
 
     #[test]
     fn part_1_sample2() {
-        assert_eq!(part_1(inputs::SAMPLE2), 2024);
+        assert_eq!(part_1(&parse(inputs::SAMPLE2).unwrap()), Ok(2024));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 53755311654662);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(53755311654662));
+    }
+
+    #[test]
+    fn part_2_real() {
+        // part_2 checks that the swapped wires make the adder add up
+        assert_eq!(part_2(&parse(&inputs::real()).unwrap()), Ok(()));
     }
 
-    /*#[test]
+    #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 0);
-    }*/
+        let err = Error::NoAnswer("no z05 and dkr wires to swap".to_string());
+        assert_eq!(part_2(&parse(inputs::SAMPLE).unwrap()), Err(err));
+    }
 
     #[test]
-    fn part_2_real() {
-        // assertions in part_2 verify that swapped inputs make the adder function properly
-        part_2(inputs::REAL);
-        //assert_eq!(part_2(inputs::REAL), 0);
+    fn loops() {
+        let gates = parse("x00: 1\n\nx00 AND b -> a\nx00 OR a -> b\nx00 XOR x00 -> z00").unwrap();
+        let err = Error::NoAnswer("the wiring loops, so 2 gates never get both inputs".to_string());
+        assert_eq!(part_1(&gates), Err(err));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("x00: 1\ny00: 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "day 24, line 2: expected `<wire>: <0 or 1>`: \"y00: 2\"");
+        let err = parse("x00: 1\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!(err.line, 3);
+        let err = parse("x00: 1\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!(err.to_string(), "day 24, line 3: no wire \"y00\" to take as input: \"x00 AND y00 -> z00\"");
     }
 }
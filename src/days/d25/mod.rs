@@ -1,65 +1,78 @@
+use crate::error::{Error, ParseError};
 use crate::grid::Grid;
+use crate::helpers::Line;
+
+const DAY: u32 = 25;
 
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(25) }
 }
 
 #[derive(Debug)]
-struct Schema {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schema {
     heights: Vec<i32>,
     grid: Grid<char>
 }
 
-fn parse(input: &str) -> (Vec<Schema>, Vec<Schema>) {
-    let mut lines = crate::helpers::to_lines(input);
-    lines.push("".to_string());
-
+pub fn parse(input: &str) -> Result<(Vec<Schema>, Vec<Schema>), ParseError> {
     let mut grid_lines: Vec<String> = Vec::new();
+    // the first line of the schema in `grid_lines`, for errors
+    let mut first: Option<Line> = None;
 
     let mut locks: Vec<Schema> = Vec::new();
     let mut keys: Vec<Schema> = Vec::new();
 
-    for line in &lines {
-        if line == "" {
-            let grid = Grid::from_lines(grid_lines.clone());
-            let is_lock = grid.get(0,0).unwrap() == '#';
-
-            let mut heights: Vec<i32> = Vec::new();
-
-            for x in (0..grid.get_width()) {
-                let top = grid.get(x,0).unwrap();
-                for y in (1..grid.get_height()) {
-                    if top != grid.get(x,y).unwrap() {
-                        if is_lock {
-                            heights.push(y - 1);
-                        } else {
-                            heights.push(grid.get_height() - 1 - y);
-                        }
-                        break;
-                    }
-                }
+    for line in crate::helpers::lines(DAY, input) {
+        if line.text.is_empty() {
+            if let Some(first) = first.take() {
+                let (is_lock, schema) = parse_schema(&grid_lines, first)?;
+                if is_lock { locks.push(schema) } else { keys.push(schema) }
+                grid_lines.clear();
             }
+        } else {
+            first.get_or_insert(line);
+            grid_lines.push(line.text.to_string());
+        }
+    }
+    if let Some(first) = first {
+        let (is_lock, schema) = parse_schema(&grid_lines, first)?;
+        if is_lock { locks.push(schema) } else { keys.push(schema) }
+    }
+
+    Ok((locks, keys))
+}
 
-            let schema = Schema { grid, heights };
+// returns whether the schema is a lock, along with the schema
+fn parse_schema(grid_lines: &[String], first: Line) -> Result<(bool, Schema), ParseError> {
+    let grid = Grid::from_lines(grid_lines.to_vec());
+    let is_lock = grid.get(0,0) == Some('#');
 
-            if is_lock {
-                locks.push(schema);
-            } else {
-                keys.push(schema);
-            }
+    let mut heights: Vec<i32> = Vec::new();
 
-            grid_lines.clear();
-        } else {
-            grid_lines.push(line.clone());
+    // the pins are where each column changes from the top row's char
+    for col in grid.transpose().rows() {
+        if let Some(y) = col.iter().position(|c| *c != col[0]) {
+            let y = y as i32;
+            heights.push(if is_lock { y - 1 } else { grid.get_height() - 1 - y });
         }
     }
 
-    (locks, keys)
+    // part 1 compares all 5 pins
+    if heights.len() != 5 {
+        return Err(first.error("expected a lock or key with 5 pins"));
+    }
+
+    Ok((is_lock, Schema { grid, heights }))
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let (locks, keys) = parse(input);
+pub fn part_1(parsed: &(Vec<Schema>, Vec<Schema>)) -> Result<i32, Error> {
+    let (locks, keys) = parsed;
 
     /*
     for lock in &locks {
@@ -90,12 +103,13 @@ pub fn part_1(input: &str) -> i32 {
         }
     }
 
-    fit
+    Ok(fit)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let (locks, keys) = parse(input);
-    0
+// there is no puzzle for part 2 on the last day
+pub fn part_2(parsed: &(Vec<Schema>, Vec<Schema>)) -> Result<(), Error> {
+    let (locks, keys) = parsed;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -104,22 +118,11 @@ mod tests {
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 3);
+        assert_eq!(part_1(&parse(inputs::SAMPLE).unwrap()), Ok(3));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 2993);
-    }
-/*
-    #[test]
-    fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 0);
-    }
-
-    #[test]
-    fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 0);
+        assert_eq!(part_1(&parse(&inputs::real()).unwrap()), Ok(2993));
     }
-*/
 }
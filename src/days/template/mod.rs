@@ -1,45 +1,57 @@
+use crate::error::{Error, ParseError};
+
+const DAY: u32 = 0;
+
 pub mod inputs {
-    pub const REAL: &str = include_str!("real");
     pub const SAMPLE: &str = include_str!("sample");
+
+    pub const ALL: &[(&str, &str)] = &[("sample", SAMPLE)];
+
+    #[cfg(test)]
+    pub fn real() -> String { crate::input::real(super::DAY) }
 }
 
-fn parse(input: &str) -> parsed_type {
-    let lines = crate::helpers::to_lines(input);
-    let parsed = // parse...
-    parsed
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut parsed: Vec<String> = Vec::new();
+    for line in crate::helpers::lines(DAY, input) {
+        // `line.num(s)?` to read a number, `line.error(message)` for anything unexpected
+        parsed.push(line.text.to_string());
+    }
+    Ok(parsed)
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let parsed = parse(input);
-    0
+pub fn part_1(parsed: &[String]) -> Result<i32, Error> {
+    Ok(0)
 }
 
-pub fn part_2(input: &str) -> i32 {
-    let parsed = parse(input);
-    0
+pub fn part_2(parsed: &[String]) -> Result<i32, Error> {
+    Ok(0)
 }
 
+// Record answers in answers.toml as they're found, e.g. `[dNN.part_1]` `sample = 11`. Until then
+// these pass without checking anything.
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::check;
 
     #[test]
     fn part_1_sample() {
-        assert_eq!(part_1(inputs::SAMPLE), 0);
+        check(DAY, 1, "sample", || inputs::SAMPLE.into(), |input| part_1(&parse(input)?));
     }
 
     #[test]
     fn part_1_real() {
-        assert_eq!(part_1(inputs::REAL), 0);
+        check(DAY, 1, "real", inputs::real, |input| part_1(&parse(input)?));
     }
 
     #[test]
     fn part_2_sample() {
-        assert_eq!(part_2(inputs::SAMPLE), 0);
+        check(DAY, 2, "sample", || inputs::SAMPLE.into(), |input| part_2(&parse(input)?));
     }
 
     #[test]
     fn part_2_real() {
-        assert_eq!(part_2(inputs::REAL), 0);
+        check(DAY, 2, "real", inputs::real, |input| part_2(&parse(input)?));
     }
 }
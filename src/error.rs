@@ -0,0 +1,72 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::days;
+use crate::input;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    UnknownDay(u32),
+    UnknownPart(u32),
+    UnknownInput { day: u32, name: String },
+    Io { path: PathBuf, message: String },
+    Answers { path: PathBuf, message: String },
+    // a puzzle parameter that doesn't exist for the day or doesn't parse
+    Param(String),
+    Parse(ParseError),
+    // `new-day` can't add the day
+    Scaffold(String),
+    // the input parsed, but there's no answer to find in it, e.g. no path to the goal
+    NoAnswer(String),
+    Panic(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownDay(day) => write!(f, "unknown day {}", day),
+            Error::UnknownPart(part) => write!(f, "unknown part {} (expected 1 or 2)", part),
+            Error::UnknownInput { day, name } => {
+                let names = days::get(*day).map(input::names).unwrap_or_default();
+                write!(f, "unknown input {:?} for day {} (available: {})", name, day, names.join(", "))
+            }
+            Error::Io { path, message } => write!(f, "can't read {}: {}", path.display(), message),
+            Error::Answers { path, message } => write!(f, "bad answers file {}: {}", path.display(), message),
+            Error::Param(message) => write!(f, "{}", message),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Scaffold(message) => write!(f, "{}", message),
+            Error::NoAnswer(message) => write!(f, "no answer: {}", message),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self { Error::Parse(e) }
+}
+
+// Malformed puzzle input. `line` counts from 1, and is 0 when the problem isn't on any one line,
+// e.g. the input is empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, text: &str, message: impl Into<String>) -> Self {
+        Self { day, line, text: text.to_string(), message: message.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "day {}: {}", self.day, self.message)
+        } else {
+            write!(f, "day {}, line {}: {}: {:?}", self.day, self.line, self.message, self.text)
+        }
+    }
+}
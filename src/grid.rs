@@ -1,12 +1,135 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+pub mod image;
+mod point;
+mod ray;
+mod regions;
+pub mod render;
+pub mod search;
+pub mod snapshot;
+mod sparse;
+mod transform;
+
+pub use point::{Dir4, Dir8, Point};
+pub use ray::Ray;
+pub use regions::Region;
+pub use sparse::SparseGrid;
+
+// A 2D grid that grows in any direction as cells are set. Cells are stored row by row in one
+// `Vec`, every row the same width and padded with the default. `origin` is the position of the
+// top-left cell, which can be negative. A `wrapping` grid is a torus instead: it never grows, and
+// positions off one edge come back on the opposite one.
 #[derive(Clone,Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GridRepr<T>"))]
 pub struct Grid<T> {
-    rows: Vec<Vec<T>>,
+    cells: Vec<T>,
+    origin: (i32, i32),
     width: i32,
     height: i32,
-    default: T
+    default: T,
+    wrapping: bool
+}
+
+// what a grid deserializes from, which is only a grid if the cells fill it
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GridRepr<T> {
+    cells: Vec<T>,
+    origin: (i32, i32),
+    width: i32,
+    height: i32,
+    default: T,
+    wrapping: bool
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<GridRepr<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(r: GridRepr<T>) -> Result<Self, String> {
+        let size = usize::try_from(r.width).ok().zip(usize::try_from(r.height).ok()).map(|(w, h)| w * h);
+        if size != Some(r.cells.len()) {
+            return Err(format!("{} cells don't fill a {}x{} grid", r.cells.len(), r.width, r.height));
+        }
+        let GridRepr { cells, origin, width, height, default, wrapping } = r;
+        Ok(Self { cells, origin, width, height, default, wrapping })
+    }
+}
+
+// What a dense `Grid` and a `SparseGrid` have in common, so neighbours, searches and rendering
+// work on either
+pub trait GridLike<T> {
+    // the cell at `p`, or none if it's off the grid. A sparse grid has a cell everywhere
+    fn cell(&self, p: Point) -> Option<&T>;
+
+    // set a cell, growing the grid if it needs to
+    fn put(&mut self, c: T, p: Point);
+
+    // the top-left and bottom-right corners of the box around the stored cells, if there are any
+    fn bounds(&self) -> Option<(Point, Point)>;
+
+    // every cell of a dense grid, or the cells that have been set in a sparse one
+    fn stored(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_>;
+
+    // what unset cells are
+    fn default_cell(&self) -> &T;
+
+    // where `p` really is, for grids that wrap around
+    fn wrap(&self, p: Point) -> Point { p }
+
+    // the points around `p` that are on the grid, with their cells
+    fn adjacent(&self, p: Point, diagonals: bool) -> Vec<(Point, &T)> {
+        let points: Vec<Point> = if diagonals {
+            p.neighbors8().map(|(_, n)| n).collect()
+        } else {
+            p.neighbors4().map(|(_, n)| n).collect()
+        };
+        points.into_iter().filter_map(|n| Some((self.wrap(n), self.cell(n)?))).collect()
+    }
+
+    // the points up, down, left and right of `p` that are on the grid and `passable`, for searches
+    fn moves<'a>(&'a self, p: Point, passable: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = Point> + 'a
+    where
+        Self: Sized,
+    {
+        p.neighbors4().map(|(_, n)| self.wrap(n)).filter(move |&n| self.cell(n).is_some_and(&passable))
+    }
+}
+
+impl<T: Clone> GridLike<T> for Grid<T> {
+    fn cell(&self, p: Point) -> Option<&T> {
+        self.get_ref_at(p)
+    }
+
+    fn put(&mut self, c: T, p: Point) {
+        self.set_at(c, p);
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        if self.width == 0 || self.height == 0 { return None }
+        let min = Point::from(self.origin);
+        Some((min, min + Point::new(self.width - 1, self.height - 1)))
+    }
+
+    fn stored(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(self.iter().map(|(c, x, y)| (Point::new(x, y), c)))
+    }
+
+    fn default_cell(&self) -> &T {
+        &self.default
+    }
+
+    fn wrap(&self, p: Point) -> Point {
+        self.wrap_point(p)
+    }
 }
 
 impl Grid<char> {
+    // not `FromStr`, since it can't fail
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         let lines = s.lines().map(String::from).collect();
         Self::from_lines(lines)
@@ -17,76 +140,215 @@ impl Grid<char> {
         Self::from_rows(rows)
     }
 
+    // ragged rows are padded out to the widest one with '.'
     pub fn from_rows(rows: Vec<Vec<char>>) -> Self {
-        let mut g = Self {
-            width: 0,
-            height: 0,
-            rows,
-            default: '.'
-        };
-        g.update_dims();
-        g
+        Self::from_cells(rows, '.')
     }
 
-    pub fn draw(&self, clear: bool) {
-        if (clear) {
-            std::process::Command::new("clear").status().unwrap();
-        }
-        for row in &self.rows {
-            for c in row {
-                print!("{}", c);
-            }
-            print!("\n");
-        }
+    // A grid of `day`'s input where every char has to pass `allowed`, with `expected` saying which
+    // do for the error, e.g. "'#' or '.'".
+    pub fn parse_chars(day: u32, input: &str, expected: &str, allowed: impl Fn(char) -> bool) -> Result<Self, ParseError> {
+        Self::try_parse_with(day, input, '.', |c| {
+            if allowed(c) { Ok(c) } else { Err(format!("unexpected {:?} (expected {})", c, expected)) }
+        })
     }
 }
 
-impl<T: Clone> Grid<T> {
+impl<T> Grid<T> {
     pub fn get_width(&self) -> i32 { self.width }
     pub fn get_height(&self) -> i32 { self.height }
 
+    pub fn get_origin(&self) -> (i32, i32) { self.origin }
+
+    // Make the grid wrap around at its edges, or stop wrapping. `get`, `set`, neighbours and
+    // searches then take positions modulo the width and height.
+    pub fn wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+
+    pub fn is_wrapping(&self) -> bool { self.wrapping }
+
+    // everywhere is in a wrapping grid, unless it's empty
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        if self.wrapping { return self.width > 0 && self.height > 0 }
+        let (ox, oy) = self.origin;
+        x >= ox && x < ox + self.width && y >= oy && y < oy + self.height
+    }
+
+    // The position (x, y) is stored at: itself, or wrapped onto the grid if it's wrapping. That's
+    // where something moving off an edge of a torus ends up.
+    pub fn wrap(&self, x: i32, y: i32) -> (i32, i32) {
+        if !self.wrapping || self.width == 0 || self.height == 0 { return (x, y) }
+        let (ox, oy) = self.origin;
+        (ox + (x - ox).rem_euclid(self.width), oy + (y - oy).rem_euclid(self.height))
+    }
+
+    pub fn wrap_point(&self, p: Point) -> Point {
+        Point::from(self.wrap(p.x, p.y))
+    }
+
+    // where (x, y) is in `cells`
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if !self.contains(x, y) { return None }
+        let (x, y) = self.wrap(x, y);
+        Some(((y - self.origin.1) * self.width + (x - self.origin.0)) as usize)
+    }
+
+    pub fn get_ref(&self, x: i32, y: i32) -> Option<&T> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut T> {
+        self.index(x, y).map(|i| &mut self.cells[i])
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool { self.contains(p.x, p.y) }
+    pub fn get_ref_at(&self, p: Point) -> Option<&T> { self.get_ref(p.x, p.y) }
+    pub fn get_mut_at(&mut self, p: Point) -> Option<&mut T> { self.get_mut(p.x, p.y) }
+
+    // every cell with its position, row by row
+    pub fn iter(&self) -> GridIter<'_, T> {
+        GridIter { cells: self.cells.iter().enumerate(), origin: self.origin, width: self.width }
+    }
+
+    pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
+        GridIterMut { cells: self.cells.iter_mut().enumerate(), origin: self.origin, width: self.width }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` doesn't allow 0, and there are no cells to chunk anyway
+        self.cells.chunks(self.width.max(1) as usize)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        // like `rows`, a grid without cells has none, even if it's wider than 0
+        let width = if self.cells.is_empty() { 0 } else { self.width as usize };
+        (0..width).map(move |x| self.cells[x..].iter().step_by(width))
+    }
+
+    // Every position in the grid, row by row. Doesn't borrow the grid, so cells can be set while
+    // walking it.
+    pub fn enumerate_positions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let (ox, oy) = self.origin;
+        let width = self.width;
+        (oy..oy + self.height).flat_map(move |y| (ox..ox + width).map(move |x| (x, y)))
+    }
+
+    // the position of the nth cell in `cells`
+    fn position(&self, i: usize) -> (i32, i32) {
+        position(self.origin, self.width, i)
+    }
+}
+
+fn position(origin: (i32, i32), width: i32, i: usize) -> (i32, i32) {
+    (origin.0 + i as i32 % width, origin.1 + i as i32 / width)
+}
+
+impl<T: Clone> Grid<T> {
     pub fn fresh(default: T, width: i32, height: i32) -> Self {
-        let mut g = Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Self {
             width,
             height,
-            rows: Vec::new(),
-            default: default.clone(),
-        };
-        for (_, x, y) in g.iter() {
-            g.set(default.clone(), x, y);
+            origin: (0, 0),
+            cells: vec![default.clone(); (width * height) as usize],
+            default,
+            wrapping: false,
         }
-        g
     }
 
-    fn update_dims(&mut self) {
-        self.width = self.rows.iter().map(|r| r.len()).max().unwrap_or(0) as i32;
-        self.height = self.rows.len() as i32;
+    // a copy of the cell. `get_ref` borrows it instead
+    pub fn get(&self, x: i32, y: i32) -> Option<T> {
+        self.get_ref(x, y).cloned()
     }
 
-    pub fn get(&self, x: i32, y: i32) -> Option<T> {
-        match self.rows.get(y as usize) {
-            Some(row) => row.get(x as usize).cloned(), //.copied(),
-            None => None
+    // ragged rows are padded out to the widest one with `default`
+    pub fn from_cells(rows: Vec<Vec<T>>, default: T) -> Self {
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let height = rows.len() as i32;
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for mut row in rows {
+            row.resize(width, default.clone());
+            cells.append(&mut row);
+        }
+        Self {
+            width: width as i32,
+            height,
+            origin: (0, 0),
+            cells,
+            default,
+            wrapping: false
         }
     }
 
-    // NOTE: does not update_dims()
-    // NOTE: started to allow setting negatives,
-    // but vecs don't appear to have negative indices,
-    // so we'll have to shift everything up in that case.
-    // Could track origin and then offset all returned x/y,
-    // e.g. if origin is (-2,-2) then the rows[1][1] is (-1,-1), origin + (x,y)
-    pub fn set(&mut self, c: T, x: i32, y: i32) -> () {
-        let y_len = (y + 1) as usize;
-        let x_len = (x + 1) as usize;
-        while self.rows.len() < y_len {
-            self.rows.push(Vec::<T>::new());
+    // A grid of puzzle input with each char mapped to a cell, e.g. digits to heights:
+    //
+    //     Grid::parse_with(input, 0, |c| c.to_digit(10).unwrap_or(0) as u8)
+    pub fn parse_with(input: &str, default: T, mut f: impl FnMut(char) -> T) -> Self {
+        let rows = input.lines().map(|line| line.chars().map(&mut f).collect()).collect();
+        Self::from_cells(rows, default)
+    }
+
+    // Like `parse_with`, but `f` can reject a char, which is reported as an error on its line of
+    // `day`'s input. So is a row that isn't as wide as the first, rather than padding it.
+    pub fn try_parse_with(
+        day: u32, input: &str, default: T, mut f: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in crate::helpers::lines(day, input) {
+            let row = line.text.chars().enumerate().map(|(x, c)| {
+                f(c).map_err(|e| line.error(format!("column {}: {}", x + 1, e)))
+            });
+            let row = row.collect::<Result<Vec<T>, ParseError>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(line.error(format!("{} wide, but the first row is {}", row.len(), first.len())));
+                }
+            }
+            rows.push(row);
         }
-        // TODO: fix other rows to match?
-        while self.rows[y as usize].len() < x_len {
-            self.rows[y as usize].push(self.default.clone());
+        Ok(Self::from_cells(rows, default))
+    }
+
+    pub fn get_at(&self, p: Point) -> Option<T> { self.get(p.x, p.y) }
+
+    // Set a cell, growing the grid with default cells to reach it if needed. Growing left or up
+    // moves the origin, so every cell keeps its coordinates.
+    pub fn set(&mut self, c: T, x: i32, y: i32) {
+        self.grow_to(x, y);
+        let i = self.index(x, y).unwrap();
+        self.cells[i] = c;
+    }
+
+    pub fn set_at(&mut self, c: T, p: Point) { self.set(c, p.x, p.y) }
+
+    fn grow_to(&mut self, x: i32, y: i32) {
+        if self.contains(x, y) { return }
+        // an empty grid starts at the first cell set in it, rather than stretching back to its origin
+        if self.width == 0 || self.height == 0 {
+            self.cells = vec![self.default.clone()];
+            self.origin = (x, y);
+            self.width = 1;
+            self.height = 1;
+            return;
         }
-        self.rows[y as usize][x as usize] = c;
+        let (ox, oy) = self.origin;
+        let min = (ox.min(x), oy.min(y));
+        let max = ((ox + self.width - 1).max(x), (oy + self.height - 1).max(y));
+        let (width, height) = (max.0 - min.0 + 1, max.1 - min.1 + 1);
+
+        let mut cells = vec![self.default.clone(); (width * height) as usize];
+        if self.width > 0 {
+            for (row, old) in self.cells.chunks(self.width as usize).enumerate() {
+                let start = ((oy + row as i32 - min.1) * width + (ox - min.0)) as usize;
+                cells[start..start + old.len()].clone_from_slice(old);
+            }
+        }
+        self.cells = cells;
+        self.origin = min;
+        self.width = width;
+        self.height = height;
     }
 
     pub fn neighbors(&self, x: i32, y: i32, diagonals: bool) -> Vec<(Option<T>, i32, i32)> {
@@ -105,66 +367,275 @@ impl<T: Clone> Grid<T> {
         }
         arr
     }
+}
 
-    pub fn iter(&self) -> GridIter<T> {
-        GridIter::new(&self)
+impl<T: Clone + TryFrom<char>> Grid<T> where T::Error: std::fmt::Display {
+    // Like `parse_with`, converting each char with `TryFrom<char>`, e.g. to an enum of tiles.
+    // Reports the first char that doesn't convert as an error on its line of `day`'s input.
+    pub fn parse(day: u32, input: &str, default: T) -> Result<Self, ParseError> {
+        Self::try_parse_with(day, input, default, |c| T::try_from(c).map_err(|e| e.to_string()))
     }
 }
 
 impl<T: std::cmp::PartialEq + Clone> Grid<T> {
     pub fn find(&self, d: T) -> Option<(i32, i32)> {
-        for (oc, x, y) in self.iter() {
-            if let Some(c) = oc {
-                if c == d { return Some((x, y)); }
-            }
-        }
-        return None;
+        self.cells.iter().position(|c| *c == d).map(|i| self.position(i))
+    }
+
+    pub fn find_point(&self, d: T) -> Option<Point> {
+        self.find(d).map(Point::from)
     }
 
     pub fn count(&self, d: T) -> i32 {
-        let mut count = 0;
-        for (oc, x, y) in self.iter() {
-            if let Some(c) = oc {
-                if c == d { count += 1 }
-            }
-        }
-        count
+        self.cells.iter().filter(|c| **c == d).count() as i32
     }
 }
 
-pub struct GridIter<T> {
-    grid: Grid<T>,
-    x: i32,
-    y: i32,
+impl<T> Index<(i32, i32)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (i32, i32)) -> &T {
+        match self.get_ref(x, y) {
+            Some(c) => c,
+            None => panic!("({}, {}) is outside the grid", x, y),
+        }
+    }
 }
 
-impl<T: Clone> GridIter<T> {
-    fn new(grid: &Grid<T>) -> Self {
-        Self {
-            // not cloning here introduces a compiler error, something to do with lifetimes
-            grid: grid.clone(),
-            x: -1, // first next() will inc x to 0
-            y: 0
+impl<T> IndexMut<(i32, i32)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (i32, i32)) -> &mut T {
+        match self.index(x, y) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("({}, {}) is outside the grid", x, y),
         }
     }
 }
 
-impl<T: Clone> Iterator for GridIter<T> {
-    type Item = (Option<T>, i32, i32);
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T { &self[(p.x, p.y)] }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T { &mut self[(p.x, p.y)] }
+}
+
+// Borrows the cells of a grid, with their positions
+pub struct GridIter<'a, T> {
+    cells: std::iter::Enumerate<std::slice::Iter<'a, T>>,
+    origin: (i32, i32),
+    width: i32,
+}
+
+impl<'a, T> Iterator for GridIter<'a, T> {
+    type Item = (&'a T, i32, i32);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut x = self.x + 1;
-        let mut y = self.y;
-        if x >= self.grid.width {
-            x = 0;
-            y = y + 1;
+        let (i, c) = self.cells.next()?;
+        let (x, y) = position(self.origin, self.width, i);
+        Some((c, x, y))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.cells.size_hint() }
+}
+
+pub struct GridIterMut<'a, T> {
+    cells: std::iter::Enumerate<std::slice::IterMut<'a, T>>,
+    origin: (i32, i32),
+    width: i32,
+}
+
+impl<'a, T> Iterator for GridIterMut<'a, T> {
+    type Item = (&'a mut T, i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, c) = self.cells.next()?;
+        let (x, y) = position(self.origin, self.width, i);
+        Some((c, x, y))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.cells.size_hint() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_rows() {
+        let grid = Grid::from_str("ab\nc");
+        assert_eq!((grid.get_width(), grid.get_height()), (2, 2));
+        assert_eq!(grid.get(1, 1), Some('.'));
+        assert_eq!(grid.get(2, 1), None);
+    }
+
+    #[test]
+    fn grow_right_and_down() {
+        let mut grid = Grid::fresh('.', 2, 2);
+        grid.set('#', 3, 2);
+        assert_eq!((grid.get_width(), grid.get_height()), (4, 3));
+        assert_eq!(grid.get(3, 2), Some('#'));
+        assert_eq!(grid.get(3, 0), Some('.'));
+        assert_eq!(grid.iter().count(), 12);
+    }
+
+    #[test]
+    fn grow_left_and_up() {
+        let mut grid = Grid::from_str("ab\ncd");
+        grid.set('#', -2, -1);
+        assert_eq!(grid.get_origin(), (-2, -1));
+        assert_eq!((grid.get_width(), grid.get_height()), (4, 3));
+        assert_eq!(grid.get(-2, -1), Some('#'));
+        assert_eq!(grid.get(1, 1), Some('d'));
+        assert_eq!(grid.get(-3, 0), None);
+        assert_eq!(grid.find('a'), Some((0, 0)));
+
+        let cells: Vec<(i32, i32)> = grid.iter().map(|(_, x, y)| (x, y)).take(5).collect();
+        assert_eq!(cells, [(-2, -1), (-1, -1), (0, -1), (1, -1), (-2, 0)]);
+    }
+
+    #[test]
+    fn borrowing() {
+        let mut grid = Grid::from_str("ab\ncd");
+        assert_eq!(grid.get_ref(1, 0), Some(&'b'));
+        assert_eq!(grid[(0, 1)], 'c');
+        *grid.get_mut(0, 1).unwrap() = 'x';
+        grid[(1, 1)] = 'y';
+        assert_eq!(grid.get_ref(-1, 0), None);
+        assert_eq!(grid.get_mut(2, 0), None);
+        assert_eq!(grid.iter().map(|(c, _, _)| c).collect::<String>(), "abxy");
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0) is outside the grid")]
+    fn index_outside() {
+        let _ = Grid::from_str("ab\ncd")[(2, 0)];
+    }
+
+    #[test]
+    fn iterators() {
+        let mut grid = Grid::from_str("ab\ncd\nef");
+        grid.set('g', -1, 0);
+        let cells: Vec<(char, i32, i32)> = grid.iter().map(|(&c, x, y)| (c, x, y)).take(4).collect();
+        assert_eq!(cells, [('g', -1, 0), ('a', 0, 0), ('b', 1, 0), ('.', -1, 1)]);
+
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["gab", ".cd", ".ef"]);
+        let cols: Vec<String> = grid.cols().map(|col| col.collect()).collect();
+        assert_eq!(cols, ["g..", "ace", "bdf"]);
+        for empty in [Grid::fresh('.', 3, 0), Grid::fresh('.', 0, 3), Grid::fresh('.', 0, 0)] {
+            assert_eq!((empty.rows().count(), empty.cols().count()), (0, 0));
+        }
+
+        for (c, x, y) in grid.iter_mut() {
+            if x == 0 { *c = c.to_ascii_uppercase() }
         }
-        if y >= self.grid.height {
-            return None;
+        assert_eq!(grid.cols().nth(1).unwrap().collect::<String>(), "ACE");
+
+        let positions: Vec<(i32, i32)> = grid.enumerate_positions().collect();
+        assert_eq!(positions.len(), 9);
+        assert_eq!(positions[..4], [(-1, 0), (0, 0), (1, 0), (-1, 1)]);
+        for (x, y) in grid.enumerate_positions() {
+            grid.set('#', x, y);
         }
-        self.x = x;
-        self.y = y;
-        Some( (self.grid.get(x, y), x, y) )
+        assert_eq!(grid.count('#'), 9);
+    }
+
+    #[test]
+    fn points() {
+        let mut grid = Grid::from_str("ab\ncd");
+        let d = grid.find_point('d').unwrap();
+        assert_eq!(d, Point::new(1, 1));
+        assert_eq!(grid[d.step(Dir4::U)], 'b');
+        grid[d.step(Dir4::L)] = 'x';
+        assert_eq!(grid.get_at(Point::new(0, 1)), Some('x'));
+        grid.set_at('#', d + Point::new(1, 1));
+        assert_eq!(grid.get_ref_at(Point::new(2, 2)), Some(&'#'));
+        assert!(!grid.contains_point(Point::new(-1, 0)));
     }
-}
 
+    #[derive(Clone, Debug, PartialEq)]
+    enum Tile { Wall, Open }
+
+    impl TryFrom<char> for Tile {
+        type Error = String;
+
+        fn try_from(c: char) -> Result<Self, String> {
+            match c {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Open),
+                _ => Err(format!("unexpected tile {:?}", c)),
+            }
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let heights = Grid::parse_with("012\n9", u8::MAX, |c| c.to_digit(10).map_or(u8::MAX, |d| d as u8));
+        assert_eq!(heights.rows().collect::<Vec<_>>(), [&[0, 1, 2][..], &[9, u8::MAX, u8::MAX][..]]);
+
+        let tiles: Grid<Tile> = Grid::parse(3, "#.\n.#", Tile::Wall).unwrap();
+        assert_eq!(tiles.get(1, 0), Some(Tile::Open));
+        assert_eq!(tiles.get(1, 1), Some(Tile::Wall));
+        assert_eq!(Grid::<Tile>::parse(3, "#.\n.", Tile::Wall).unwrap_err().to_string(),
+            "day 3, line 2: 1 wide, but the first row is 2: \".\"");
+        assert_eq!(Grid::<Tile>::parse(3, "#.\n.x", Tile::Wall).unwrap_err().to_string(),
+            "day 3, line 2: column 2: unexpected tile 'x': \".x\"");
+
+        let maze = Grid::parse_chars(3, "#.\n.#", "'#' or '.'", |c| "#.".contains(c)).unwrap();
+        assert_eq!((maze.get(1, 0), maze.get(0, 1)), (Some('.'), Some('.')));
+        assert_eq!(Grid::parse_chars(3, "#.\n#x", "'#' or '.'", |c| "#.".contains(c)).unwrap_err().to_string(),
+            "day 3, line 2: column 2: unexpected 'x' (expected '#' or '.'): \"#x\"");
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut grid = Grid::from_str("ab\ncd\nef").wrapping(true);
+        assert_eq!(grid.get(2, 0), Some('a'));
+        assert_eq!(grid.get(-1, -1), Some('f'));
+        assert_eq!(grid.wrap(7, -4), (1, 2));
+        assert_eq!(grid.neighbors(0, 0, false).iter().filter_map(|(c, _, _)| *c).collect::<String>(), "becb");
+
+        // setting off the edge wraps instead of growing
+        grid.set('#', 3, 3);
+        assert_eq!((grid.get_width(), grid.get_height()), (2, 3));
+        assert_eq!(grid.get(1, 0), Some('#'));
+
+        let grid = grid.wrapping(false);
+        assert_eq!(grid.get(2, 0), None);
+    }
+
+    #[test]
+    fn grow_empty() {
+        let mut grid = Grid::fresh(0, 0, 0);
+        grid.set(5, 50, 50);
+        assert_eq!((grid.get_width(), grid.get_height()), (1, 1));
+        assert_eq!(grid.get_origin(), (50, 50));
+        grid.set(6, 52, 51);
+        assert_eq!((grid.get_width(), grid.get_height()), (3, 2));
+        assert_eq!(grid.count(0), 4);
+        assert_eq!(grid.get(50, 50), Some(5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut grid = Grid::from_str("#.\n.^");
+        grid.set('>', -1, 0);
+        let json = serde_json::to_string(&grid).unwrap();
+        let back: Grid<char> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.get_origin(), (-1, 0));
+        assert_eq!(back.iter().map(|(c, _, _)| *c).collect::<String>(), ">#...^");
+
+        // the cells have to fill the grid, or it would index out of them
+        let short = json.replace(r#"">","#, "");
+        let err = serde_json::from_str::<Grid<char>>(&short).unwrap_err();
+        assert!(err.to_string().starts_with("5 cells don't fill a 3x2 grid"));
+        let negative = json.replace(r#""height":2"#, r#""height":-2"#);
+        assert!(serde_json::from_str::<Grid<char>>(&negative).is_err());
+
+        assert_eq!(serde_json::to_string(&(Point::new(3, -2), Dir4::L)).unwrap(), r#"[{"x":3,"y":-2},"L"]"#);
+        assert_eq!(serde_json::from_str::<Dir8>(r#""UR""#).unwrap(), Dir8::UR);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::Grid;
+use crate::error::Error;
+
+// Grids as images, each cell a square of pixels coloured by a palette function. Frames from a
+// simulation can be written as a numbered sequence to flip through in an image viewer.
+
+pub type Rgb = [u8; 3];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    // binary PPM (P6), which any viewer reads and is trivial to write
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // The image's width and height in pixels, and its RGB bytes row by row. Each cell is `scale`
+    // pixels square.
+    pub fn to_pixels(&self, scale: u32, palette: impl Fn(&T) -> Rgb) -> (u32, u32, Vec<u8>) {
+        let (width, height) = (self.width as u32 * scale, self.height as u32 * scale);
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for row in self.rows() {
+            let line: Vec<u8> = row.iter()
+                .flat_map(|c| palette(c).repeat(scale as usize))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        (width, height, pixels)
+    }
+
+    pub fn write_image(&self, out: impl Write, format: Format, scale: u32, palette: impl Fn(&T) -> Rgb) -> io::Result<()> {
+        let (width, height, pixels) = self.to_pixels(scale, palette);
+        match format {
+            Format::Ppm => write_ppm(out, width, height, &pixels),
+            Format::Png => write_png(out, width, height, &pixels),
+        }
+    }
+
+    // the format is picked by the extension, `.ppm` or `.png`
+    pub fn save_image(&self, path: &Path, scale: u32, palette: impl Fn(&T) -> Rgb) -> Result<(), Error> {
+        let io_error = |message: String| Error::Io { path: path.to_path_buf(), message };
+        let format = Format::from_path(path)
+            .ok_or_else(|| io_error("expected a .ppm or .png file".to_string()))?;
+        let file = fs::File::create(path).map_err(|e| io_error(e.to_string()))?;
+        self.write_image(io::BufWriter::new(file), format, scale, palette)
+            .map_err(|e| io_error(e.to_string()))
+    }
+}
+
+fn write_ppm(mut out: impl Write, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+    out.write_all(pixels)?;
+    out.flush()
+}
+
+fn write_png(out: impl Write, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(pixels).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+// Numbered images of a simulation, `frame_00000.png`, `frame_00001.png` and so on, e.g. to find
+// the Christmas tree on day 14 or to stitch into a GIF with another tool.
+pub struct Frames {
+    dir: PathBuf,
+    format: Format,
+    scale: u32,
+    count: usize,
+}
+
+impl Frames {
+    // creates `dir` if it's missing
+    pub fn new(dir: impl Into<PathBuf>, format: Format, scale: u32) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| Error::Io { path: dir.clone(), message: e.to_string() })?;
+        Ok(Self { dir, format, scale, count: 0 })
+    }
+
+    // write the next frame, returning its path
+    pub fn write<T>(&mut self, grid: &Grid<T>, palette: impl Fn(&T) -> Rgb) -> Result<PathBuf, Error> {
+        let path = self.dir.join(format!("frame_{:05}.{}", self.count, self.format.extension()));
+        grid.save_image(&path, self.scale, palette)?;
+        self.count += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(c: &char) -> Rgb {
+        if *c == '#' { [255, 255, 255] } else { [0, 0, 0] }
+    }
+
+    #[test]
+    fn ppm() {
+        let grid = Grid::from_str("#.\n..");
+        let mut out = Vec::new();
+        grid.write_image(&mut out, Format::Ppm, 1, palette).unwrap();
+        assert_eq!(out, [b"P6\n2 2\n255\n".as_slice(), &[255, 255, 255], &[0; 9]].concat());
+
+        let (width, height, pixels) = grid.to_pixels(2, palette);
+        assert_eq!((width, height, pixels.len()), (4, 4, 48));
+        assert_eq!(pixels[..12], [255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(pixels[12..24], pixels[..12]);
+    }
+
+    #[test]
+    fn png_frames() {
+        let dir = crate::helpers::temp_dir("frames");
+        let mut frames = Frames::new(&dir, Format::Png, 3).unwrap();
+        let mut grid = Grid::fresh('.', 3, 2);
+        for x in 0..3 {
+            grid.set('#', x, 1);
+            frames.write(&grid, palette).unwrap();
+        }
+        let last = dir.join("frame_00002.png");
+        assert!(last.exists());
+        assert!(fs::read(&last).unwrap().starts_with(b"\x89PNG"));
+
+        let bad = dir.join("frame.gif");
+        assert!(matches!(grid.save_image(&bad, 1, palette), Err(Error::Io { .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A position in a grid, or the offset between two positions. x grows to the right and y grows
+// down, like the rows of the puzzle input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ZERO: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    // steps between two points moving only up, down, left and right
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // the point one step away in `dir`
+    pub fn step(self, dir: impl Into<Dir8>) -> Self {
+        self + dir.into().offset()
+    }
+
+    // the 4 points next to this one, clockwise from up
+    pub fn neighbors4(self) -> impl Iterator<Item = (Dir4, Point)> {
+        Dir4::ALL.into_iter().map(move |d| (d, self + d.offset()))
+    }
+
+    // the 8 points around this one, clockwise from up
+    pub fn neighbors8(self) -> impl Iterator<Item = (Dir8, Point)> {
+        Dir8::ALL.into_iter().map(move |d| (d, self + d.offset()))
+    }
+
+    // the points on the line from here to `other`, both ends included, by Bresenham's algorithm
+    pub fn line_to(self, other: Point) -> Vec<Point> {
+        let (dx, dy) = ((other.x - self.x).abs(), -(other.y - self.y).abs());
+        let (sx, sy) = ((other.x - self.x).signum(), (other.y - self.y).signum());
+        let mut err = dx + dy;
+        let mut p = self;
+        let mut line = vec![p];
+        while p != other {
+            let e2 = 2 * err;
+            if e2 >= dy { err += dy; p.x += sx }
+            if e2 <= dx { err += dx; p.y += sy }
+            line.push(p);
+        }
+        line
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+// up, right, down, left
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dir4 { U, R, D, L }
+
+impl Dir4 {
+    // clockwise from up
+    pub const ALL: [Dir4; 4] = [Dir4::U, Dir4::R, Dir4::D, Dir4::L];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    // one step in this direction
+    pub fn offset(self) -> Point {
+        match self {
+            Dir4::U => Point::new(0, -1),
+            Dir4::R => Point::new(1, 0),
+            Dir4::D => Point::new(0, 1),
+            Dir4::L => Point::new(-1, 0),
+        }
+    }
+
+    // the direction of a one step offset, if it's up, right, down or left
+    pub fn from_offset(offset: Point) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.offset() == offset)
+    }
+
+    // `^`, `>`, `v` or `<`, as the puzzles draw them
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::U => '^',
+            Dir4::R => '>',
+            Dir4::D => 'v',
+            Dir4::L => '<',
+        }
+    }
+
+    pub fn from_arrow(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.arrow() == c)
+    }
+}
+
+// the 4 directions plus the diagonals between them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dir8 { U, UR, R, DR, D, DL, L, UL }
+
+impl Dir8 {
+    // clockwise from up
+    pub const ALL: [Dir8; 8] = [Dir8::U, Dir8::UR, Dir8::R, Dir8::DR, Dir8::D, Dir8::DL, Dir8::L, Dir8::UL];
+
+    // an eighth of a turn
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn offset(self) -> Point {
+        match self {
+            Dir8::U => Point::new(0, -1),
+            Dir8::UR => Point::new(1, -1),
+            Dir8::R => Point::new(1, 0),
+            Dir8::DR => Point::new(1, 1),
+            Dir8::D => Point::new(0, 1),
+            Dir8::DL => Point::new(-1, 1),
+            Dir8::L => Point::new(-1, 0),
+            Dir8::UL => Point::new(-1, -1),
+        }
+    }
+
+    pub fn from_offset(offset: Point) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.offset() == offset)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Self {
+        Dir8::ALL[d as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(b - a, Point::new(3, -4));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(Point::from((1, 2)), a);
+        assert_eq!(<(i32, i32)>::from(b), (4, -2));
+    }
+
+    #[test]
+    fn lines() {
+        let a = Point::new(0, 0);
+        assert_eq!(a.line_to(a), [a]);
+        assert_eq!(a.line_to(Point::new(-2, 2)), [a, Point::new(-1, 1), Point::new(-2, 2)]);
+        let line = a.line_to(Point::new(4, 1));
+        assert_eq!(line, [a, Point::new(1, 0), Point::new(2, 1), Point::new(3, 1), Point::new(4, 1)]);
+        let mut back = Point::new(4, 1).line_to(a);
+        back.reverse();
+        assert_eq!(back.len(), line.len());
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Dir4::L.turn_right(), Dir4::U);
+        assert_eq!(Dir4::U.turn_left(), Dir4::L);
+        assert_eq!(Dir4::R.reverse(), Dir4::L);
+        assert_eq!(Dir8::UL.turn_right(), Dir8::U);
+        assert_eq!(Dir8::U.turn_left(), Dir8::UL);
+        assert_eq!(Dir8::DR.reverse(), Dir8::UL);
+        for d in Dir4::ALL {
+            assert_eq!(Dir4::from_offset(d.offset()), Some(d));
+            assert_eq!(Dir8::from(d).offset(), d.offset());
+            assert_eq!(Dir4::from_arrow(d.arrow()), Some(d));
+        }
+        assert_eq!(Dir4::from_offset(Point::new(1, 1)), None);
+        assert_eq!(Dir8::from_offset(Point::new(1, 1)), Some(Dir8::DR));
+        assert_eq!(Point::new(2, 2).step(Dir4::U), Point::new(2, 1));
+        assert_eq!(Point::new(2, 2).neighbors8().filter(|(d, _)| d.is_diagonal()).count(), 4);
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Grid, Point};
+
+// Looking along lines through a grid: rays out from a point, the first thing a ray hits, and the
+// cells on a straight line between two points.
+
+// The cells from a point outwards, one `step` at a time, not counting the point itself. Stops at
+// the edge of the grid, so it never stops on a wrapping grid.
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    at: Point,
+    step: Point,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == Point::ZERO { return None }
+        self.at += self.step;
+        let c = self.grid.get_ref_at(self.at)?;
+        Some((self.grid.wrap_point(self.at), c))
+    }
+}
+
+impl<T> Grid<T> {
+    // e.g. `grid.ray(p, Dir4::U.offset())` for everything above `p`
+    pub fn ray(&self, from: Point, step: Point) -> Ray<'_, T> {
+        Ray { grid: self, at: from, step }
+    }
+
+    // the first cell along a ray that `hit` matches, if it doesn't leave the grid first
+    pub fn first_hit(&self, from: Point, step: Point, hit: impl Fn(&T) -> bool) -> Option<(Point, &T)> {
+        self.ray(from, step).find(|(_, c)| hit(c))
+    }
+
+    // The cells on the straight line from `a` to `b`, both included, that are on the grid. Lines
+    // that aren't straight or diagonal are as close as cells can get.
+    pub fn line(&self, a: Point, b: Point) -> impl Iterator<Item = (Point, &T)> {
+        a.line_to(b).into_iter().filter_map(|p| Some((p, self.get_ref_at(p)?)))
+    }
+
+    // whether nothing between `a` and `b` `blocks` the view from one to the other
+    pub fn line_of_sight(&self, a: Point, b: Point, blocks: impl Fn(&T) -> bool) -> bool {
+        !self.line(a, b).any(|(p, c)| p != a && p != b && blocks(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Dir4, Dir8};
+
+    #[test]
+    fn rays() {
+        let grid = Grid::from_str("..#.\n.X..\n....\n#...");
+        let x = grid.find_point('X').unwrap();
+        let right: Vec<Point> = grid.ray(x, Dir4::R.offset()).map(|(p, _)| p).collect();
+        assert_eq!(right, [Point::new(2, 1), Point::new(3, 1)]);
+        assert_eq!(grid.ray(x, Dir8::UL.offset()).count(), 1);
+        assert_eq!(grid.ray(x, Point::ZERO).count(), 0);
+        // every other cell on the way down
+        assert_eq!(grid.ray(Point::new(0, -1), Dir4::D.offset() * 2).map(|(_, c)| *c).collect::<String>(), ".#");
+
+        assert_eq!(grid.first_hit(x, Dir8::UR.offset(), |c| *c == '#'), Some((Point::new(2, 0), &'#')));
+        assert_eq!(grid.first_hit(x, Dir4::U.offset(), |c| *c == '#'), None);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::from_str("a...\n.#..\n...b");
+        let (a, b) = (Point::new(0, 0), Point::new(3, 2));
+        assert_eq!(grid.line(a, b).map(|(_, c)| *c).collect::<String>(), "a#.b");
+        assert!(!grid.line_of_sight(a, b, |c| *c == '#'));
+        assert!(grid.line_of_sight(a, Point::new(3, 0), |c| *c == '#'));
+        assert!(grid.line_of_sight(Point::new(1, 1), b, |c| *c == '#'));
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+
+use super::{Dir4, Grid, Point};
+
+// A connected group of cells with the same value, like a garden plot on day 12
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "RegionRepr<T>"))]
+pub struct Region<T> {
+    pub value: T,
+    // row by row
+    pub cells: Vec<Point>,
+    // the same cells for lookups, which are rebuilt from them on deserializing
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    members: HashSet<Point>,
+}
+
+// what a region deserializes from
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RegionRepr<T> {
+    value: T,
+    cells: Vec<Point>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<RegionRepr<T>> for Region<T> {
+    fn from(r: RegionRepr<T>) -> Self {
+        Self::new(r.value, r.cells)
+    }
+}
+
+impl<T> Region<T> {
+    fn new(value: T, mut cells: Vec<Point>) -> Self {
+        cells.sort_by_key(|p| (p.y, p.x));
+        let members = cells.iter().copied().collect();
+        Self { value, cells, members }
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.members.contains(&p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // the edges between a cell in the region and one outside it
+    pub fn perimeter(&self) -> usize {
+        self.cells.iter()
+            .flat_map(|p| p.neighbors4())
+            .filter(|&(_, n)| !self.contains(n))
+            .count()
+    }
+
+    // Straight runs of perimeter, counted by their corners: a polygon has as many sides as
+    // corners. Each cell has a corner between two neighbouring directions if both are outside
+    // (sticking out), or both are inside but the diagonal between them isn't (tucked in).
+    pub fn sides(&self) -> usize {
+        let mut corners = 0;
+        for &p in &self.cells {
+            for d in Dir4::ALL {
+                let (a, b) = (p.step(d), p.step(d.turn_right()));
+                let diagonal = p + d.offset() + d.turn_right().offset();
+                match (self.contains(a), self.contains(b)) {
+                    (false, false) => corners += 1,
+                    (true, true) if !self.contains(diagonal) => corners += 1,
+                    _ => (),
+                }
+            }
+        }
+        corners
+    }
+
+    // the top-left and bottom-right corners of the smallest box around the region
+    pub fn bbox(&self) -> (Point, Point) {
+        let min = Point::new(self.cells.iter().map(|p| p.x).min().unwrap(), self.cells[0].y);
+        let max = Point::new(self.cells.iter().map(|p| p.x).max().unwrap(), self.cells[self.cells.len() - 1].y);
+        (min, max)
+    }
+
+    // Groups of cells outside the region that it completely surrounds, each as its cells. Holes
+    // are connected up, down, left and right, so cells that only touch the outside diagonally
+    // are a hole.
+    pub fn holes(&self) -> Vec<Vec<Point>> {
+        let (min, max) = self.bbox();
+        // one cell of margin around the box is all connected outside
+        let (min, max) = (min - Point::new(1, 1), max + Point::new(1, 1));
+        let inside = |p: Point| p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y;
+
+        let mut seen: HashSet<Point> = self.members.clone();
+        fill(min, &mut seen, inside);
+
+        let mut holes = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let p = Point::new(x, y);
+                if seen.contains(&p) { continue }
+                let mut hole = fill(p, &mut seen, inside);
+                hole.sort_by_key(|p| (p.y, p.x));
+                holes.push(hole);
+            }
+        }
+        holes
+    }
+}
+
+// Mark everything reachable from `start` up, down, left and right that's `allowed` and not yet
+// seen. Returns the points it marked.
+fn fill(start: Point, seen: &mut HashSet<Point>, allowed: impl Fn(Point) -> bool) -> Vec<Point> {
+    let mut filled = vec![start];
+    let mut stack = vec![start];
+    seen.insert(start);
+    while let Some(p) = stack.pop() {
+        for (_, n) in p.neighbors4() {
+            if allowed(n) && seen.insert(n) {
+                filled.push(n);
+                stack.push(n);
+            }
+        }
+    }
+    filled
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    // Every region of connected cells with the same value, in the order of their first cell row by
+    // row. With `diagonals`, cells touching at a corner are connected too. On a wrapping grid a
+    // region carries on across the edges, with its cells where they are on the grid, though its
+    // perimeter and sides are still measured as if it didn't wrap.
+    pub fn components(&self, diagonals: bool) -> Vec<Region<T>> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut regions = Vec::new();
+        for i in 0..self.cells.len() {
+            if seen[i] { continue }
+            seen[i] = true;
+            let value = &self.cells[i];
+            let mut cells = Vec::new();
+            let mut stack = vec![Point::from(self.position(i))];
+            while let Some(p) = stack.pop() {
+                cells.push(p);
+                let neighbors: Vec<Point> = if diagonals {
+                    p.neighbors8().map(|(_, n)| n).collect()
+                } else {
+                    p.neighbors4().map(|(_, n)| n).collect()
+                };
+                for n in neighbors.into_iter().map(|n| self.wrap_point(n)) {
+                    let Some(j) = self.index(n.x, n.y) else { continue };
+                    if !seen[j] && self.cells[j] == *value {
+                        seen[j] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+            regions.push(Region::new(value.clone(), cells));
+        }
+        regions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions() {
+        let grid = Grid::from_str("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = grid.components(false);
+        let summary: Vec<(char, usize, usize, usize)> = regions.iter()
+            .map(|r| (r.value, r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(summary, [
+            ('A', 4, 10, 4),
+            ('B', 4, 8, 4),
+            ('C', 4, 10, 8),
+            ('D', 1, 4, 4),
+            ('E', 3, 8, 4),
+        ]);
+        assert_eq!(regions[2].bbox(), (Point::new(2, 1), Point::new(3, 3)));
+        assert!(regions[2].contains(Point::new(3, 3)));
+    }
+
+    #[test]
+    fn holes_and_diagonals() {
+        let grid = Grid::from_str("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+        let regions = grid.components(false);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0].holes().len(), 4);
+        assert_eq!(regions[0].holes()[0], [Point::new(1, 1)]);
+        assert_eq!(regions[0].sides(), 20);
+
+        let grid = Grid::from_str("X.\n.X");
+        assert_eq!(grid.components(false).len(), 4);
+        let regions = grid.components(true);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].cells, [Point::new(0, 0), Point::new(1, 1)]);
+        assert_eq!(regions[0].holes().len(), 0);
+    }
+
+    #[test]
+    fn wrapping() {
+        // the A's are one region across the left and right edges, the B's across the top and bottom
+        let grid = Grid::from_str("A.BA
+..B.
+....
+..B.").wrapping(true);
+        let regions = grid.components(false);
+        let cells = |value| regions.iter().find(|r| r.value == value).unwrap().cells.clone();
+        assert_eq!(regions.len(), 3);
+        assert_eq!(cells('A'), [Point::new(0, 0), Point::new(3, 0)]);
+        assert_eq!(cells('B'), [Point::new(2, 0), Point::new(2, 1), Point::new(2, 3)]);
+        assert_eq!(grid.wrapping(false).components(false).len(), 5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        // the lookup set isn't in the JSON, but comes back with the cells
+        let region = Grid::from_str("AB\nAA").components(false).remove(0);
+        let json = serde_json::to_string(&region).unwrap();
+        assert_eq!(json, r#"{"value":"A","cells":[{"x":0,"y":0},{"x":0,"y":1},{"x":1,"y":1}]}"#);
+        let back: Region<char> = serde_json::from_str(&json).unwrap();
+        assert!(back.contains(Point::new(1, 1)));
+        assert_eq!(back.perimeter(), region.perimeter());
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use super::{Dir4, Grid, GridLike, Point, SparseGrid};
+
+// Draws a grid to a terminal with ANSI escape codes: each cell styled by a function of its value,
+// with overlays like a path or a cursor drawn on top.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color { Black, Red, Green, Yellow, Blue, Magenta, Cyan, White, Grey }
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Grey => 90,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    // swap the foreground and background, like a terminal cursor
+    pub reverse: bool,
+}
+
+impl Style {
+    pub fn fg(color: Color) -> Self {
+        Self { fg: Some(color), ..Self::default() }
+    }
+
+    pub fn bg(color: Color) -> Self {
+        Self { bg: Some(color), ..Self::default() }
+    }
+
+    pub fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    // the SGR parameters, e.g. `1;31`. Empty for the default style
+    fn codes(&self) -> String {
+        let mut codes: Vec<String> = Vec::new();
+        if self.bold { codes.push("1".to_string()) }
+        if self.reverse { codes.push("7".to_string()) }
+        if let Some(fg) = self.fg { codes.push(fg.code().to_string()) }
+        if let Some(bg) = self.bg { codes.push((bg.code() + 10).to_string()) }
+        codes.join(";")
+    }
+}
+
+// Cells to draw over the grid, with a different glyph or just a different style
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    cells: HashMap<Point, Option<char>>,
+    style: Style,
+}
+
+impl Overlay {
+    // restyle some points, keeping what's drawn there
+    pub fn highlight(points: impl IntoIterator<Item = Point>, style: Style) -> Self {
+        Self { cells: points.into_iter().map(|p| (p, None)).collect(), style }
+    }
+
+    // draw `glyph` at each point
+    pub fn glyph(points: impl IntoIterator<Item = Point>, glyph: char, style: Style) -> Self {
+        Self { cells: points.into_iter().map(|p| (p, Some(glyph))).collect(), style }
+    }
+
+    // A path of steps up, down, left or right, each drawn as an arrow towards the next. The last
+    // point keeps what's drawn there.
+    pub fn path(points: &[Point], style: Style) -> Self {
+        let mut cells: HashMap<Point, Option<char>> = HashMap::new();
+        for step in points.windows(2) {
+            let arrow = Dir4::from_offset(step[1] - step[0]).map(|d| d.arrow());
+            cells.insert(step[0], arrow);
+        }
+        if let Some(&last) = points.last() {
+            cells.entry(last).or_insert(None);
+        }
+        Self { cells, style }
+    }
+
+    pub fn cursor(p: Point) -> Self {
+        Self::highlight([p], Style { reverse: true, ..Style::default() })
+    }
+}
+
+pub struct Renderer<'a, T> {
+    grid: &'a dyn GridLike<T>,
+    style: Box<dyn Fn(&T) -> Style + 'a>,
+    overlays: Vec<Overlay>,
+    clear: bool,
+}
+
+impl<'a, T: Display> Renderer<'a, T> {
+    // every cell unstyled, as `Display` draws it
+    pub fn new(grid: &'a dyn GridLike<T>) -> Self {
+        Self { grid, style: Box::new(|_| Style::default()), overlays: Vec::new(), clear: false }
+    }
+
+    pub fn style(mut self, style: impl Fn(&T) -> Style + 'a) -> Self {
+        self.style = Box::new(style);
+        self
+    }
+
+    // drawn in the order they're added, so later overlays win
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    // clear the screen first, for animating
+    pub fn clear(mut self, clear: bool) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.clear {
+            // clear the screen and move the cursor to the top left
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        let Some((min, max)) = self.grid.bounds() else { return Ok(()) };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let p = Point::new(x, y);
+                let c = self.grid.cell(p).unwrap_or(self.grid.default_cell());
+                let mut glyph = c.to_string();
+                let mut style = (self.style)(c);
+                for overlay in &self.overlays {
+                    if let Some(over) = overlay.cells.get(&p) {
+                        if let Some(g) = over { glyph = g.to_string() }
+                        style = overlay.style;
+                    }
+                }
+                match style.codes().as_str() {
+                    "" => write!(out, "{}", glyph)?,
+                    codes => write!(out, "\x1b[{}m{}\x1b[0m", codes, glyph)?,
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        self.write_to(&mut io::stdout().lock()).unwrap();
+    }
+}
+
+// the rendered grid, escape codes and all
+impl<T: Display> Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut out = Vec::new();
+        self.write_to(&mut out).map_err(|_| std::fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&out))
+    }
+}
+
+impl<T: Clone + Display> SparseGrid<T> {
+    // the box around the cells that are set
+    pub fn render(&self) -> Renderer<'_, T> {
+        Renderer::new(self)
+    }
+}
+
+impl<T: Clone + Display> Grid<T> {
+    pub fn render(&self) -> Renderer<'_, T> {
+        Renderer::new(self)
+    }
+
+    // print the grid unstyled, clearing the screen first if animating
+    pub fn draw(&self, clear: bool) {
+        self.render().clear(clear).print();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain() {
+        let grid = Grid::from_str("#.\n.#");
+        assert_eq!(grid.render().to_string(), "#.\n.#\n");
+        assert_eq!(grid.render().clear(true).to_string(), "\x1b[2J\x1b[H#.\n.#\n");
+        assert_eq!(Grid::parse_with("12\n3", 0, |c| c.to_digit(10).unwrap()).render().to_string(), "12\n30\n");
+    }
+
+    #[test]
+    fn styles_and_overlays() {
+        let grid = Grid::from_str("#..\n...");
+        let walls = |c: &char| if *c == '#' { Style::fg(Color::Red).bold() } else { Style::default() };
+        assert_eq!(grid.render().style(walls).to_string(), "\x1b[1;31m#\x1b[0m..\n...\n");
+
+        let path = [Point::new(1, 0), Point::new(1, 1), Point::new(2, 1)];
+        let out = grid.render()
+            .overlay(Overlay::path(&path, Style::fg(Color::Green)))
+            .overlay(Overlay::cursor(Point::new(2, 1)))
+            .to_string();
+        assert_eq!(out, "#\x1b[32mv\x1b[0m.\n.\x1b[32m>\x1b[0m\x1b[7m.\x1b[0m\n");
+
+        let out = grid.render().overlay(Overlay::glyph([Point::new(0, 1)], 'O', Style::bg(Color::Blue))).to_string();
+        assert_eq!(out, "#..\n\x1b[44mO\x1b[0m..\n");
+    }
+}
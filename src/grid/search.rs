@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::Point;
+
+// Shortest paths over any state: a grid position, or a position plus facing, or anything else
+// that can list the states one move away. The caller decides what's passable and what each move
+// costs through the `successors` closure.
+
+// What a search found: the cost to reach each state it saw, the state each was reached from, and
+// the goal state if it got there.
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    pub costs: HashMap<S, i32>,
+    pub prev: HashMap<S, S>,
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Self { costs: HashMap::from([(start, 0)]), prev: HashMap::new(), goal: None }
+    }
+
+    pub fn cost(&self, state: &S) -> Option<i32> {
+        self.costs.get(state).copied()
+    }
+
+    // the states from a start to `state`, following the predecessors back
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) { return None }
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_cost(&self) -> Option<i32> {
+        self.goal.as_ref().and_then(|g| self.cost(g))
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+}
+
+// Breadth-first search where every move costs 1. Stops at the first state that `is_goal`, or
+// maps every reachable state if none does.
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I, mut is_goal: impl FnMut(&S) -> bool) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        let cost = search.costs[&state] + 1;
+        for next in successors(&state) {
+            if search.costs.contains_key(&next) { continue }
+            search.costs.insert(next.clone(), cost);
+            search.prev.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+    search
+}
+
+// Dijkstra's algorithm, with `successors` giving each next state and the cost of moving to it.
+pub fn dijkstra<S, I>(start: S, successors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i32)>,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+// A*: Dijkstra's algorithm guided by `heuristic`, an estimate of the cost left to reach a goal.
+// It must never overestimate, or the path found might not be the shortest.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> i32,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i32)>,
+{
+    let mut search = Search::new(start.clone());
+    let mut heap = BinaryHeap::from([Entry { priority: heuristic(&start), cost: 0, state: start }]);
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        // already reached more cheaply since this was queued
+        if search.costs.get(&state).is_some_and(|&c| cost > c) { continue }
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.costs.get(&next).is_some_and(|&c| c <= next_cost) { continue }
+            search.costs.insert(next.clone(), next_cost);
+            search.prev.insert(next.clone(), state.clone());
+            heap.push(Entry { priority: next_cost + heuristic(&next), cost: next_cost, state: next });
+        }
+    }
+    search
+}
+
+// a state waiting in the heap, ordered so the lowest priority comes out first
+struct Entry<S> {
+    priority: i32,
+    cost: i32,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Dir4, Grid, GridLike};
+
+    const MAZE: &str = "S.#\n#.#\n..E";
+
+    #[test]
+    fn bfs_paths() {
+        let grid = Grid::from_str(MAZE);
+        let start = grid.find_point('S').unwrap();
+        let end = grid.find_point('E').unwrap();
+        let search = bfs(start, |&p| grid.moves(p, |c| *c != '#'), |&p| p == end);
+        assert_eq!(search.goal_cost(), Some(4));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.len(), 5);
+
+        // no goal maps everything reachable
+        let search = bfs(start, |&p| grid.moves(p, |c| *c != '#'), |_| false);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.costs.len(), 6);
+        assert_eq!(search.cost(&Point::new(0, 2)), Some(4));
+        assert_eq!(search.path_to(&Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn weighted_with_facing() {
+        // turning costs 1000, like day 16. Both ways round turn twice, so going down first is
+        // cheaper because it's fewer steps.
+        let grid = Grid::from_str("S....\n.###.\n..E..");
+        let start = (grid.find_point('S').unwrap(), Dir4::R);
+        let end = grid.find_point('E').unwrap();
+        let successors = |&(p, facing): &(Point, Dir4)| {
+            p.neighbors4()
+                .filter(|&(_, n)| grid.get_at(n).is_some_and(|c| c != '#'))
+                .map(move |(d, n)| ((n, d), if d == facing { 1 } else { 1001 }))
+                .collect::<Vec<_>>()
+        };
+        let search = dijkstra(start, successors, |&(p, _)| p == end);
+        assert_eq!(search.goal_cost(), Some(2004));
+
+        let search = astar(start, successors, |&(p, _)| p.manhattan(end), |&(p, _)| p == end);
+        assert_eq!(search.goal_cost(), Some(2004));
+        assert_eq!(search.goal_path().unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,141 @@
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+
+use super::render::{Color, Overlay, Style};
+use super::{Grid, GridLike, Point};
+
+// Comparing grids cell by cell, and snapshot tests that check a grid against a text fixture, so a
+// failing test shows which cells went wrong and not just that the final number did.
+
+impl<T: Clone + PartialEq> Grid<T> {
+    // the points where the grids differ, row by row, with cells off either grid counted as its default
+    pub fn diff(&self, other: &Grid<T>) -> Vec<Point> {
+        let bounds = [self.bounds(), other.bounds()].into_iter().flatten().reduce(|(min, max), (min2, max2)| {
+            (Point::new(min.x.min(min2.x), min.y.min(min2.y)), Point::new(max.x.max(max2.x), max.y.max(max2.y)))
+        });
+        let Some((min, max)) = bounds else { return Vec::new() };
+        let mut changed = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let p = Point::new(x, y);
+                let a = self.get_ref_at(p).unwrap_or(&self.default);
+                let b = other.get_ref_at(p).unwrap_or(&other.default);
+                if a != b { changed.push(p) }
+            }
+        }
+        changed
+    }
+}
+
+// `src/days/dNN/snapshots/<name>.txt`
+pub fn path(day: u32, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/days")
+        .join(format!("d{:02}", day))
+        .join("snapshots")
+        .join(format!("{}.txt", name))
+}
+
+// panics with a side by side diff if the grid doesn't match day `day`'s fixture `name`
+pub fn assert_snapshot<T: Clone + Display>(day: u32, name: &str, grid: &Grid<T>) {
+    if let Err(message) = check(&path(day, name), grid) {
+        panic!("{}", message);
+    }
+}
+
+// The grid, drawn as text, against the fixture at `path`. Fixtures are only written when
+// `UPDATE_SNAPSHOTS` is set, to be checked by eye and committed, so a missing one is a failure.
+pub fn check<T: Clone + Display>(path: &Path, grid: &Grid<T>) -> Result<(), String> {
+    check_or_update(path, grid, env::var_os("UPDATE_SNAPSHOTS").is_some())
+}
+
+fn check_or_update<T: Clone + Display>(path: &Path, grid: &Grid<T>, update: bool) -> Result<(), String> {
+    let actual = grid.render().to_string();
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {}", dir.display(), e))?;
+        }
+        return fs::write(path, actual).map_err(|e| format!("can't write {}: {}", path.display(), e));
+    }
+    if !path.exists() {
+        return Err(format!("{} is missing (run with UPDATE_SNAPSHOTS=1 to write it)", path.display()));
+    }
+    let expected = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    if expected == actual {
+        return Ok(());
+    }
+
+    // the exact text differs, though maybe not in any cell, e.g. a missing row of default cells
+    let (expected, actual) = (Grid::from_str(&expected), Grid::from_str(&actual));
+    let mut message = format!("{} doesn't match\n{}", path.display(), side_by_side(&expected, &actual));
+    let [expected, actual] = [&expected, &actual].map(|g| (g.get_width(), g.get_height()));
+    if expected != actual {
+        message += &format!("\nexpected {}x{} cells, got {}x{}", expected.0, expected.1, actual.0, actual.1);
+    }
+    Err(message)
+}
+
+// the expected grid with changed cells in red, next to the actual grid with them in green
+pub fn side_by_side(expected: &Grid<char>, actual: &Grid<char>) -> String {
+    let changed = expected.diff(actual);
+    let left = expected.render().overlay(Overlay::highlight(changed.clone(), Style::bg(Color::Red))).to_string();
+    let right = actual.render().overlay(Overlay::highlight(changed.clone(), Style::bg(Color::Green))).to_string();
+    let (left, right): (Vec<&str>, Vec<&str>) = (left.lines().collect(), right.lines().collect());
+
+    let width = (expected.width as usize).max("expected".len());
+    let mut out = format!("{:<width$}   actual\n", "expected");
+    for i in 0..left.len().max(right.len()) {
+        // the escape codes take up no room, so pad by how many cells were drawn
+        let drawn = if i < left.len() { expected.width as usize } else { 0 };
+        let line = left.get(i).copied().unwrap_or("");
+        out += &format!("{}{}   {}\n", line, " ".repeat(width - drawn), right.get(i).copied().unwrap_or(""));
+    }
+    let shown = changed.iter().take(10).join(", ");
+    let more = if changed.len() > 10 { ", ..." } else { "" };
+    out += &format!("{} cells differ: {}{}", changed.len(), shown, more);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs() {
+        let a = Grid::from_str("#..\n.O.");
+        let b = Grid::from_str("#.O\n...\n..#");
+        assert_eq!(a.diff(&a), []);
+        assert_eq!(a.diff(&b), [Point::new(2, 0), Point::new(1, 1), Point::new(2, 2)]);
+        assert_eq!(b.diff(&a), a.diff(&b));
+
+        let out = side_by_side(&a, &b);
+        assert!(out.starts_with("expected   actual\n#.\x1b[41m.\x1b[0m        #.\x1b[42mO\x1b[0m\n"));
+        assert!(out.ends_with("3 cells differ: (2, 0), (1, 1), (2, 2)"));
+    }
+
+    #[test]
+    fn snapshots() {
+        let dir = crate::helpers::temp_dir("snapshots");
+        let path = dir.join("grid.txt");
+        let grid = Grid::from_str("#.\n.#");
+        assert!(check_or_update(&path, &grid, false).unwrap_err().contains("grid.txt is missing"));
+        assert!(!path.exists());
+        assert_eq!(check_or_update(&path, &grid, true), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#.\n.#\n");
+        assert_eq!(check_or_update(&path, &grid, false), Ok(()));
+
+        let err = check_or_update(&path, &Grid::from_str("#.\n.."), false).unwrap_err();
+        assert!(err.ends_with("1 cells differ: (1, 1)"));
+
+        // a trailing row or column of '.' is no different cell by cell, but still doesn't match
+        let err = check_or_update(&path, &Grid::from_str("#.\n.#\n.."), false).unwrap_err();
+        assert!(err.ends_with("0 cells differ: \nexpected 2x2 cells, got 2x3"));
+        let err = check_or_update(&path, &Grid::from_str("#..\n.#."), false).unwrap_err();
+        assert!(err.ends_with("expected 2x2 cells, got 3x2"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use super::{Grid, GridLike, Point};
+
+// A grid that only stores the cells that have been set, with every other point the default. It
+// has no edges, so it suits a few points scattered over a big or unbounded space.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self { cells: HashMap::new(), default }
+    }
+
+    // the cell at `p`, which is the default if it was never set
+    pub fn get_ref(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, c: T, p: Point) {
+        self.cells.insert(p, c);
+    }
+
+    // back to the default
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn is_set(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    // how many cells are set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // the cells that are set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, c)| (*p, c))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn get(&self, p: Point) -> T {
+        self.get_ref(p).clone()
+    }
+
+    // the cells of `grid` that are `keep`, e.g. the antennas on an otherwise empty map
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let mut sparse = Self::new(grid.default.clone());
+        for (c, x, y) in grid.iter() {
+            if keep(c) { sparse.set(c.clone(), Point::new(x, y)) }
+        }
+        sparse
+    }
+
+    // a dense grid covering every cell that's set
+    pub fn to_dense(&self) -> Grid<T> {
+        let Some((min, max)) = self.bounds() else { return Grid::fresh(self.default.clone(), 0, 0) };
+        let mut grid = Grid::fresh(self.default.clone(), max.x - min.x + 1, max.y - min.y + 1);
+        grid.origin = (min.x, min.y);
+        for (p, c) in self.iter() {
+            grid.set_at(c.clone(), p);
+        }
+        grid
+    }
+}
+
+impl<T: Clone> GridLike<T> for SparseGrid<T> {
+    fn cell(&self, p: Point) -> Option<&T> {
+        Some(self.get_ref(p))
+    }
+
+    fn put(&mut self, c: T, p: Point) {
+        self.set(c, p);
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        let xs = self.cells.keys().map(|p| p.x);
+        let ys = self.cells.keys().map(|p| p.y);
+        Some((Point::new(xs.clone().min()?, ys.clone().min()?), Point::new(xs.max()?, ys.max()?)))
+    }
+
+    fn stored(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        Box::new(self.iter())
+    }
+
+    fn default_cell(&self) -> &T {
+        &self.default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::search::bfs;
+
+    #[test]
+    fn sparse() {
+        let mut grid = SparseGrid::new('.');
+        grid.set('#', Point::new(-1000, 5));
+        grid.set('#', Point::new(1000, -5));
+        assert_eq!(grid.get(Point::new(0, 0)), '.');
+        assert_eq!(grid.get(Point::new(1000, -5)), '#');
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Point::new(-1000, -5), Point::new(1000, 5))));
+        assert_eq!(grid.remove(Point::new(1000, -5)), Some('#'));
+        assert_eq!(grid.get(Point::new(1000, -5)), '.');
+        assert_eq!(SparseGrid::new(0).bounds(), None);
+    }
+
+    #[test]
+    fn either_backend() {
+        let dense = Grid::from_str("a..\n..b");
+        let sparse = SparseGrid::from_grid(&dense, |c| *c != '.');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.render().to_string(), dense.render().to_string());
+        assert_eq!(sparse.to_dense().rows().count(), 2);
+
+        // the dense grid only covers the cells that are set, however far they are from the origin
+        let mut far = SparseGrid::new('.');
+        far.set('#', Point::new(1000, 1000));
+        far.set('#', Point::new(1001, 1000));
+        let far = far.to_dense();
+        assert_eq!((far.get_width(), far.get_height()), (2, 1));
+        assert_eq!(far.get_origin(), (1000, 1000));
+        assert_eq!(far.get(1001, 1000), Some('#'));
+        assert_eq!(SparseGrid::new('.').to_dense().get_width(), 0);
+
+        // both have every neighbour inside the 3x2 box, but the sparse grid goes on past it
+        fn count_adjacent(grid: &dyn GridLike<char>) -> usize { grid.adjacent(Point::new(0, 0), true).len() }
+        assert_eq!(count_adjacent(&dense), 3);
+        assert_eq!(count_adjacent(&sparse), 8);
+
+        // a search bounded by what's passable works on either
+        let walls = |c: &char| *c != '#';
+        let mut sparse = SparseGrid::new('#');
+        for x in 0..5 { sparse.set('.', Point::new(x, 0)) }
+        let search = bfs(Point::new(0, 0), |&p| sparse.moves(p, walls), |_| false);
+        assert_eq!(search.costs.len(), 5);
+    }
+}
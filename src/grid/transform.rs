@@ -0,0 +1,144 @@
+use super::{Grid, Point};
+
+// Whole-grid transforms. Each builds a new grid in one pass over the cells, starting at (0, 0)
+// whatever the origin of the grid it came from.
+
+impl<T: Clone> Grid<T> {
+    // a `width` x `height` grid with each cell from `f(x, y)`
+    fn build(width: i32, height: i32, default: T, mut f: impl FnMut(i32, i32) -> T) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        let mut cells = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Self { cells, origin: (0, 0), width, height, default, wrapping: false }
+    }
+
+    // the cell at (x, y) counting from the top-left corner, wherever the origin is
+    fn local(&self, x: i32, y: i32) -> &T {
+        &self.cells[(y * self.width + x) as usize]
+    }
+
+    // rows become columns, so (x, y) moves to (y, x)
+    pub fn transpose(&self) -> Self {
+        Self::build(self.height, self.width, self.default.clone(), |x, y| self.local(y, x).clone())
+    }
+
+    // a quarter turn clockwise
+    pub fn rotate_right(&self) -> Self {
+        let h = self.height;
+        Self::build(self.height, self.width, self.default.clone(), |x, y| self.local(y, h - 1 - x).clone())
+    }
+
+    // a quarter turn anticlockwise
+    pub fn rotate_left(&self) -> Self {
+        let w = self.width;
+        Self::build(self.height, self.width, self.default.clone(), |x, y| self.local(w - 1 - y, x).clone())
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let (w, h) = (self.width, self.height);
+        Self::build(w, h, self.default.clone(), |x, y| self.local(w - 1 - x, h - 1 - y).clone())
+    }
+
+    // mirrored left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        Self::build(w, self.height, self.default.clone(), |x, y| self.local(w - 1 - x, y).clone())
+    }
+
+    // mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        Self::build(self.width, h, self.default.clone(), |x, y| self.local(x, h - 1 - y).clone())
+    }
+
+    // The `width` x `height` part of the grid with its top-left corner at `corner`, in the grid's
+    // coordinates. Any of it outside the grid is the default.
+    pub fn crop(&self, corner: Point, width: i32, height: i32) -> Self {
+        Self::build(width, height, self.default.clone(), |x, y| {
+            self.get(corner.x + x, corner.y + y).unwrap_or_else(|| self.default.clone())
+        })
+    }
+
+    // every `width` x `height` part of the grid that fits inside it, with its top-left corner
+    pub fn windows(&self, width: i32, height: i32) -> impl Iterator<Item = (Point, Self)> + '_ {
+        let (ox, oy) = self.origin;
+        let (xs, ys) = (self.width - width + 1, self.height - height + 1);
+        (oy..oy + ys).flat_map(move |y| {
+            (ox..ox + xs).map(move |x| {
+                let corner = Point::new(x, y);
+                (corner, self.crop(corner, width, height))
+            })
+        })
+    }
+
+    // each cell repeated into an `sx` x `sy` block
+    pub fn scale(&self, sx: i32, sy: i32) -> Self {
+        Self::build(self.width * sx, self.height * sy, self.default.clone(), |x, y| self.local(x / sx, y / sy).clone())
+    }
+
+    // Each cell replaced by a run of cells left to right, like day 15's wider warehouse. Runs of
+    // different lengths are padded to the longest with the default.
+    pub fn widen(&self, f: impl Fn(&T) -> Vec<T>) -> Self {
+        let rows = self.rows().map(|row| row.iter().flat_map(&f).collect()).collect();
+        Self::from_cells(rows, self.default.clone())
+    }
+
+    // `nx` copies of the grid side by side, `ny` deep
+    pub fn tile(&self, nx: i32, ny: i32) -> Self {
+        let (w, h) = (self.width, self.height);
+        Self::build(w * nx, h * ny, self.default.clone(), |x, y| self.local(x % w, y % h).clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(grid: &Grid<char>) -> String {
+        grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn turns_and_flips() {
+        let grid = Grid::from_str("abc\ndef");
+        assert_eq!(text(&grid.transpose()), "ad\nbe\ncf");
+        assert_eq!(text(&grid.rotate_right()), "da\neb\nfc");
+        assert_eq!(text(&grid.rotate_left()), "cf\nbe\nad");
+        assert_eq!(text(&grid.rotate_180()), "fed\ncba");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc");
+        assert_eq!(text(&grid.rotate_right().rotate_right().rotate_right().rotate_right()), "abc\ndef");
+    }
+
+    #[test]
+    fn crops_and_windows() {
+        let mut grid = Grid::from_str("abc\ndef\nghi");
+        assert_eq!(text(&grid.crop(Point::new(1, 1), 3, 2)), "ef.\nhi.");
+        let windows: Vec<(Point, String)> = grid.windows(2, 2).map(|(p, w)| (p, text(&w))).collect();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[3], (Point::new(1, 1), "ef\nhi".to_string()));
+
+        // crops use the grid's coordinates, but come out at (0, 0)
+        grid.set('z', -1, 0);
+        let crop = grid.crop(Point::new(-1, 0), 2, 1);
+        assert_eq!((text(&crop), crop.get_origin()), ("za".to_string(), (0, 0)));
+        assert_eq!(text(&grid.rotate_180()), "ihg.\nfed.\ncbaz");
+    }
+
+    #[test]
+    fn scale_widen_tile() {
+        let grid = Grid::from_str("#O\n.@");
+        assert_eq!(text(&grid.scale(2, 1)), "##OO\n..@@");
+        let wide = grid.widen(|c| match c {
+            'O' => vec!['[', ']'],
+            '@' => vec!['@'],
+            c => vec![*c, *c],
+        });
+        assert_eq!(text(&wide), "##[]\n..@.");
+        assert_eq!(text(&grid.tile(2, 2)), "#O#O\n.@.@\n#O#O\n.@.@");
+    }
+}
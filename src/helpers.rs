@@ -1,6 +1,96 @@
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+use crate::error::ParseError;
+
 // https://doc.rust-lang.org/stable/rust-by-example/std_misc/file/read_lines.html
 pub fn to_lines(file: &str) -> Vec<String> {
     file.lines()  // split the string into an iterator of string slices
         .map(String::from)  // make each slice into a string
         .collect()  // gather them together into a vector
 }
+
+// A line of puzzle input that knows where it came from, so parsers can report exactly what they
+// couldn't understand.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub day: u32,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+// the lines of `input`, numbered from 1
+pub fn lines(day: u32, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line { day, number: i + 1, text })
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.number, self.text, message)
+    }
+
+    // parse `s`, some piece of this line, as a number
+    pub fn num<T: FromStr>(&self, s: &str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(format!("expected a number, got {:?}", s)))
+    }
+
+    // parse the whole line as a list of numbers separated by `sep`
+    pub fn nums<T: FromStr>(&self, sep: &str) -> Result<Vec<T>, ParseError> {
+        self.text.split(sep).map(|s| self.num(s)).collect()
+    }
+
+    pub fn split_once(&self, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text.split_once(sep).ok_or_else(|| self.error(format!("expected {:?}", sep)))
+    }
+
+    // match the whole line against `re`, with `expected` describing the format for the error
+    pub fn captures(&self, re: &Regex, expected: &str) -> Result<Captures<'a>, ParseError> {
+        re.captures(self.text).ok_or_else(|| self.error(format!("expected `{}`", expected)))
+    }
+}
+
+// Check `c` appears exactly once in `input`, e.g. a grid's start, with `what` naming it for the
+// error. A second one is reported on its line.
+pub fn one_marker(day: u32, input: &str, c: char, what: &str) -> Result<(), ParseError> {
+    let mut found = false;
+    for line in lines(day, input) {
+        let count = line.text.matches(c).count();
+        if count > 1 || (count == 1 && found) {
+            return Err(line.error(format!("more than one {}", what)));
+        }
+        found |= count == 1;
+    }
+    if found { Ok(()) } else { Err(ParseError::new(day, 0, "", format!("no {}", what))) }
+}
+
+// A scratch dir for a test that writes files, unique to this run and emptied of anything a failed
+// run left behind. The test removes it when it's done.
+#[cfg(test)]
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_errors() {
+        let line = lines(5, "1|2\n3,x").nth(1).unwrap();
+        assert_eq!(line.number, 2);
+        assert_eq!(line.nums::<i32>(",").unwrap_err(), ParseError::new(5, 2, "3,x", "expected a number, got \"x\""));
+        assert_eq!(line.split_once("|").unwrap_err().to_string(), "day 5, line 2: expected \"|\": \"3,x\"");
+    }
+
+    #[test]
+    fn markers() {
+        assert_eq!(one_marker(6, "..\n.^", '^', "guard '^'"), Ok(()));
+        assert_eq!(one_marker(6, "..\n..", '^', "guard '^'").unwrap_err().to_string(), "day 6: no guard '^'");
+        assert_eq!(one_marker(6, ".^\n^.", '^', "guard '^'").unwrap_err(), ParseError::new(6, 2, "^.", "more than one guard '^'"));
+        assert_eq!(one_marker(6, "^^", '^', "guard '^'").unwrap_err().line, 1);
+    }
+}
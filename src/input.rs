@@ -0,0 +1,110 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::solution::Solution;
+
+// Puzzle inputs are personal, so they are read from disk at runtime rather than built into the
+// binary. Only the samples from the puzzle text are embedded in each day module.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    // `<inputs dir>/dNN/<name>`, falling back to an embedded sample of the same name
+    Named(String),
+    File(PathBuf),
+    Stdin,
+}
+
+// `$AOC_INPUTS`, or `inputs` in the working directory
+pub fn dir() -> PathBuf {
+    dir_under(Path::new(""))
+}
+
+// `$AOC_INPUTS`, or `inputs` under `root`
+pub fn dir_under(root: &Path) -> PathBuf {
+    env::var_os("AOC_INPUTS").map(PathBuf::from).unwrap_or_else(|| root.join("inputs"))
+}
+
+pub fn path(day: u32, name: &str) -> PathBuf {
+    dir().join(format!("d{:02}", day)).join(name)
+}
+
+// embedded samples first, then anything else found on disk
+pub fn names(solution: &dyn Solution) -> Vec<String> {
+    let mut names: Vec<String> = solution.inputs().iter().map(|(name, _)| name.to_string()).collect();
+    let mut on_disk: Vec<String> = fs::read_dir(dir().join(format!("d{:02}", solution.day())))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !names.contains(name))
+        .collect();
+    on_disk.sort();
+    names.append(&mut on_disk);
+    names
+}
+
+pub fn load(solution: &dyn Solution, source: &Source) -> Result<String, Error> {
+    match source {
+        Source::Named(name) => {
+            let path = path(solution.day(), name);
+            if path.is_file() {
+                return read(&path);
+            }
+            match solution.input(name) {
+                Some(input) => Ok(input.to_string()),
+                None => Err(Error::UnknownInput { day: solution.day(), name: name.clone() }),
+            }
+        }
+        Source::File(path) => read(path),
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map_err(|e| Error::Io {
+                path: PathBuf::from("-"),
+                message: e.to_string(),
+            })?;
+            Ok(input)
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::Io { path: path.to_path_buf(), message: e.to_string() })
+}
+
+// Our own real inputs, for the tests. Read relative to the crate so `cargo test` works from
+// anywhere.
+#[cfg(test)]
+pub fn real(day: u32) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs").join(format!("d{:02}", day)).join("real");
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("can't read {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_for_day() {
+        if env::var_os("AOC_INPUTS").is_none() {
+            assert_eq!(path(7, "real"), PathBuf::from("inputs/d07/real"));
+            assert_eq!(dir_under(Path::new("/repo")), PathBuf::from("/repo/inputs"));
+        }
+    }
+
+    #[test]
+    fn embedded_sample() {
+        let solution = crate::days::get(3).unwrap();
+        let input = load(solution, &Source::Named("sample2".to_string())).unwrap();
+        assert_eq!(input, crate::days::d03::inputs::SAMPLE2);
+    }
+
+    #[test]
+    fn missing_file() {
+        let solution = crate::days::get(3).unwrap();
+        let err = load(solution, &Source::File(PathBuf::from("no/such/file"))).unwrap_err();
+        assert!(matches!(err, Error::Io { .. }));
+    }
+}
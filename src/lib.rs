@@ -0,0 +1,20 @@
+// Advent of Code 2024: the solutions, and the pieces they're built from.
+//
+// `days` has every day's `parse`, `part_1` and `part_2`, and `days::REGISTRY` runs them through
+// the `Solution` trait. `runner` loads inputs and solves, times and checks days the way the
+// `aoc_2024` binary does. `grid` and `helpers` are shared by the days and free to use elsewhere.
+
+pub mod helpers;
+pub mod grid;
+pub mod days;
+pub mod error;
+pub mod solution;
+pub mod input;
+pub mod answers;
+pub mod runner;
+pub mod scaffold;
+pub mod bench;
+
+pub use error::{Error, ParseError};
+pub use grid::Grid;
+pub use solution::{Answer, Config, Solution};
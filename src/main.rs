@@ -1,12 +1,334 @@
-mod helpers;
-mod grid;
-mod days;
+use std::process::ExitCode;
+use std::time::Duration;
 
-use days::d24 as day;
+use aoc_2024::input::{self, Source};
+use aoc_2024::{answers, bench, days, runner, scaffold};
+use aoc_2024::{Config, Error, Solution};
 
-fn main() {
-    //println!("{}", day::part_1(day::inputs::SAMPLE));
-    //println!("{}", day::part_1(day::inputs::REAL));
-    //println!("{}", day::part_2(day::inputs::SAMPLE));
-    //println!("{}", day::part_2(day::inputs::REAL));
+const USAGE: &str = "usage:
+    aoc_2024 run <day> [--part <1|2>] [--input <name> | --file <path|->] [--param <key>=<value>]...
+    aoc_2024 run-all [--input <name>]
+    aoc_2024 verify [--day <day>] [--input <name>] [--answers <path>]
+    aoc_2024 bench [--day <day>] [--time <secs>] [--save <name> | --baseline <name>]
+    aoc_2024 list
+    aoc_2024 new-day <day>
+
+Named inputs are read from $AOC_INPUTS/dNN/<name> (default ./inputs), falling back to the
+samples built into each day. `--file -` reads the input from stdin.
+Params default to the ones for the named input, or for the real input with `--file`.
+Known answers are read from $AOC_ANSWERS (default ./answers.toml).
+`bench` times each day on its real input and compares it to the last run, or to a baseline saved
+with `--save`. Baselines live in target/aoc-bench. Build with --release.
+`new-day` adds src/days/dNN from the template, with empty sample and real inputs.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cli(args: &[String]) -> Result<(), String> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("run-all") => run_all(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("list") => list(),
+        Some("new-day") => new_day(&args[1..]),
+        Some(other) => Err(format!("unknown command {:?}\n{}", other, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+// `run <day> [--part <1|2>] [--input <name> | --file <path|->] [--param <key>=<value>]...`
+// Runs both parts on the real input by default.
+fn run(args: &[String]) -> Result<(), String> {
+    let mut day: Option<u32> = None;
+    let mut parts = vec![1, 2];
+    let mut source = Source::Named("real".to_string());
+    let mut overrides: Vec<(String, String)> = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let part = args.next().ok_or("--part needs a value")?;
+                parts = vec![parse_num("part", part)?];
+            }
+            "--input" | "-i" => {
+                source = Source::Named(args.next().ok_or("--input needs a value")?.clone());
+            }
+            "--file" | "-f" => {
+                source = match args.next().ok_or("--file needs a value")?.as_str() {
+                    "-" => Source::Stdin,
+                    path => Source::File(path.into()),
+                };
+            }
+            "--param" | "-P" => {
+                let param = args.next().ok_or("--param needs a value")?;
+                let (key, value) = param.split_once('=').ok_or(format!("expected <key>=<value>, got {:?}", param))?;
+                overrides.push((key.to_string(), value.to_string()));
+            }
+            _ if day.is_none() => day = Some(parse_num("day", arg)?),
+            _ => return Err(format!("unexpected argument {:?}\n{}", arg, USAGE)),
+        }
+    }
+    let day = day.ok_or(USAGE)?;
+
+    let input = runner::input(day, &source).map_err(|e| e.to_string())?;
+    let label = match &source {
+        Source::Named(name) => name.clone(),
+        Source::File(path) => path.display().to_string(),
+        Source::Stdin => "stdin".to_string(),
+    };
+    let config = Config {
+        input: match &source {
+            Source::Named(name) => name.clone(),
+            _ => "real".to_string(),
+        },
+        overrides,
+    };
+    for part in parts {
+        let answer = runner::solve(day, part, &input, &config).map_err(|e| e.to_string())?;
+        println!("day {} part {} ({}): {}", day, part, label, answer);
+    }
+    Ok(())
+}
+
+// `run-all [--input <name>]`
+// Runs every day on the real input (or another named one) and prints how long each took.
+fn run_all(args: &[String]) -> Result<(), String> {
+    let name = match args {
+        [] => "real".to_string(),
+        [flag, name] if flag == "--input" || flag == "-i" => name.clone(),
+        _ => return Err(USAGE.to_string()),
+    };
+    let config = Config::for_input(&name);
+
+    println!("{:>3}  {:>10}  {:>10}  {:>10}  {:>10}", "day", "parse", "part 1", "part 2", "total");
+    let mut total = Duration::ZERO;
+    let mut errors: Vec<String> = Vec::new();
+    for solution in days::REGISTRY {
+        let input = match input::load(solution, &Source::Named(name.clone())) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3}  {}", solution.day(), e);
+                continue;
+            }
+        };
+        let timing = runner::time(solution, &input, &config);
+        let mut cell = |result: Result<Duration, &Error>, what: &str| match result {
+            Ok(elapsed) => runner::fmt_duration(elapsed),
+            Err(e) => {
+                errors.push(format!("day {} {}: {}", solution.day(), what, e));
+                "error".to_string()
+            }
+        };
+        let parse = cell(timing.parse.as_ref().copied(), "parse");
+        let [part_1, part_2] = match &timing.parts {
+            Some([part_1, part_2]) => [
+                cell(part_1.as_ref().map(|(_, elapsed)| *elapsed), "part 1"),
+                cell(part_2.as_ref().map(|(_, elapsed)| *elapsed), "part 2"),
+            ],
+            // not run, since there was nothing to run them on
+            None => ["-".to_string(), "-".to_string()],
+        };
+        println!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+            timing.day, parse, part_1, part_2, runner::fmt_duration(timing.total())
+        );
+        total += timing.total();
+    }
+    println!("{:>3}  {:>10}  {:>10}  {:>10}  {:>10}", "", "", "", "", runner::fmt_duration(total));
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if errors.is_empty() { Ok(()) } else { Err(format!("{} parts failed", errors.len())) }
+}
+
+// `verify [--day <day>] [--input <name>] [--answers <path>]`
+// Checks solutions against the recorded answers. Build with --release for the slow days.
+fn verify(args: &[String]) -> Result<(), String> {
+    let mut day: Option<u32> = None;
+    let mut name: Option<String> = None;
+    let mut path = answers::path();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_num("day", args.next().ok_or("--day needs a value")?)?),
+            "--input" | "-i" => name = Some(args.next().ok_or("--input needs a value")?.clone()),
+            "--answers" => path = args.next().ok_or("--answers needs a value")?.into(),
+            _ => return Err(format!("unexpected argument {:?}\n{}", arg, USAGE)),
+        }
+    }
+    let answers = answers::Answers::load(&path).map_err(|e| e.to_string())?;
+    if let Some(day) = day {
+        runner::day(day).map_err(|e| e.to_string())?;
+    }
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solution in days::REGISTRY.iter().filter(|s| day.is_none_or(|day| s.day() == day)) {
+        for part in [1, 2] {
+            let recorded: Vec<(&str, &str)> = answers
+                .for_part(solution.day(), part)
+                .into_iter()
+                .filter(|(n, _)| name.as_ref().is_none_or(|name| n == name))
+                .collect();
+            let wanted = name.as_deref().unwrap_or("real");
+            if !recorded.iter().any(|(n, _)| *n == wanted) {
+                println!("day {:>2} part {} {:<12} missing", solution.day(), part, wanted);
+                missing += 1;
+            }
+            for (n, expected) in recorded {
+                let (status, elapsed) = runner::check(solution, part, n, expected);
+                let result = match status {
+                    runner::Status::Pass => {
+                        passed += 1;
+                        format!("ok      {}", runner::fmt_duration(elapsed))
+                    }
+                    runner::Status::Fail { expected, got } => {
+                        failed += 1;
+                        format!("FAIL    expected {}, got {}", expected, got)
+                    }
+                    runner::Status::Error(e) => {
+                        failed += 1;
+                        format!("FAIL    {}", e)
+                    }
+                };
+                println!("day {:>2} part {} {:<12} {}", solution.day(), part, n, result);
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed == 0 { Ok(()) } else { Err(format!("{} answers failed", failed)) }
+}
+
+// `bench [--day <day>] [--time <secs>] [--save <name> | --baseline <name>]`
+// Benchmarks parsing and each part of every day on the real input. Like criterion, each run is
+// compared to and then saved as the `base` baseline. `--save` saves under another name instead,
+// and `--baseline` compares to one without saving.
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut day: Option<u32> = None;
+    let mut budget = Duration::from_secs(1);
+    let mut compare_to = "base".to_string();
+    let mut save_as = Some("base".to_string());
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_num("day", args.next().ok_or("--day needs a value")?)?),
+            "--time" | "-t" => {
+                let secs = args.next().ok_or("--time needs a value")?;
+                budget = secs.parse().ok().and_then(|s| Duration::try_from_secs_f64(s).ok())
+                    .ok_or(format!("invalid time {:?}", secs))?;
+            }
+            "--save" => {
+                let name = args.next().ok_or("--save needs a value")?;
+                compare_to = name.clone();
+                save_as = Some(name.clone());
+            }
+            "--baseline" => {
+                compare_to = args.next().ok_or("--baseline needs a value")?.clone();
+                save_as = None;
+            }
+            _ => return Err(format!("unexpected argument {:?}\n{}", arg, USAGE)),
+        }
+    }
+    if let Some(day) = day {
+        runner::day(day).map_err(|e| e.to_string())?;
+    }
+    let old = bench::Baseline::load(&bench::baseline_path(&compare_to)).map_err(|e| e.to_string())?;
+    let mut new = match &save_as {
+        Some(name) => bench::Baseline::load(&bench::baseline_path(name)).map_err(|e| e.to_string())?,
+        None => bench::Baseline::default(),
+    };
+
+    let config = Config::for_input("real");
+    let mut errors: Vec<String> = Vec::new();
+    for solution in days::REGISTRY.iter().filter(|s| day.is_none_or(|day| s.day() == day)) {
+        let input = match input::load(solution, &Source::Named("real".to_string())) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("d{:02}  {}", solution.day(), e);
+                continue;
+            }
+        };
+        // the parts are benchmarked on one parse of the input, so they don't include parsing
+        let parsed = runner::timed(|| solution.parse(&input)).map(|(parsed, _)| parsed);
+        for name in ["parse", "part_1", "part_2"] {
+            let stats = match (name, &parsed) {
+                ("parse", _) => bench::measure(|| solution.parse(&input), budget),
+                // parsing failed, and its benchmark reports that
+                (_, Err(_)) => continue,
+                ("part_1", Ok(parsed)) => bench::measure(|| solution.solve_parsed(1, parsed.as_ref(), &config), budget),
+                (_, Ok(parsed)) => bench::measure(|| solution.solve_parsed(2, parsed.as_ref(), &config), budget),
+            };
+            let label = format!("d{:02}/{}", solution.day(), name);
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    println!("{:<10}  error", label);
+                    errors.push(format!("{}: {}", label, e));
+                    continue;
+                }
+            };
+            let change = match old.get(solution.day(), name) {
+                Some(old) => bench::compare(old, stats.median).to_string(),
+                None => String::new(),
+            };
+            let line = format!(
+                "{:<10}  [{:>9} {:>9} {:>9}]  {}",
+                label,
+                runner::fmt_duration(stats.min),
+                runner::fmt_duration(stats.median),
+                runner::fmt_duration(stats.max),
+                change
+            );
+            println!("{}", line.trim_end());
+            new.set(solution.day(), name, stats.median);
+        }
+    }
+    println!("(min, median and max)");
+
+    if let Some(name) = &save_as {
+        new.save(&bench::baseline_path(name)).map_err(|e| e.to_string())?;
+    }
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if errors.is_empty() { Ok(()) } else { Err(format!("{} benchmarks failed", errors.len())) }
+}
+
+// every registered day and the inputs it ships with
+fn list() -> Result<(), String> {
+    for solution in days::REGISTRY {
+        let names = input::names(solution);
+        println!("day {:>2}: {}", solution.day(), names.join(", "));
+    }
+    Ok(())
+}
+
+// `new-day <day>`
+fn new_day(args: &[String]) -> Result<(), String> {
+    let [day] = args else { return Err(USAGE.to_string()) };
+    let day = parse_num("day", day)?;
+    let (src, inputs) = scaffold::dirs(scaffold::root());
+    let written = scaffold::new_day(&src, &inputs, day).map_err(|e| e.to_string())?;
+    for path in written {
+        println!("wrote {}", path.display());
+    }
+    println!("record answers in answers.toml under [d{:02}.part_1] and [d{:02}.part_2] as you find them", day, day);
+    Ok(())
+}
+
+fn parse_num(what: &str, s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("invalid {} {:?}", what, s))
 }
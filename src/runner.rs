@@ -0,0 +1,205 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::days;
+use crate::error::Error;
+use crate::input::{self, Source};
+use crate::solution::{Answer, Config, Solution};
+
+pub fn day(day: u32) -> Result<&'static dyn Solution, Error> {
+    days::get(day).ok_or(Error::UnknownDay(day))
+}
+
+pub fn input(day: u32, source: &Source) -> Result<String, Error> {
+    input::load(self::day(day)?, source)
+}
+
+pub fn solve(day: u32, part: u32, input: &str, config: &Config) -> Result<Answer, Error> {
+    self::day(day)?.solve(part, input, config)
+}
+
+// How long a day took on one input: parsing it once, then each part on what was parsed. The parts
+// only run if parsing worked.
+pub struct Timing {
+    pub day: u32,
+    pub parse: Result<Duration, Error>,
+    pub parts: Option<[Result<(Answer, Duration), Error>; 2]>,
+}
+
+impl Timing {
+    // parsing and both parts, leaving out whatever failed
+    pub fn total(&self) -> Duration {
+        let parse = self.parse.as_ref().copied().unwrap_or_default();
+        parse + self.parts.iter().flatten().flatten().map(|(_, elapsed)| *elapsed).sum::<Duration>()
+    }
+}
+
+pub fn time(solution: &dyn Solution, input: &str, config: &Config) -> Timing {
+    match timed(|| solution.parse(input)) {
+        Ok((parsed, elapsed)) => {
+            let parts = [1, 2].map(|part| timed(|| solution.solve_parsed(part, parsed.as_ref(), config)));
+            Timing { day: solution.day(), parse: Ok(elapsed), parts: Some(parts) }
+        }
+        Err(e) => Timing { day: solution.day(), parse: Err(e), parts: None },
+    }
+}
+
+// Run `f` and time it. A panic is turned into an error, so one broken day doesn't stop a run over
+// all of them.
+pub fn timed<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<(T, Duration), Error> {
+    let start = Instant::now();
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map(|value| (value, start.elapsed())),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Err(Error::Panic(message))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, got: String },
+    Error(Error),
+}
+
+// Solve one part on a named input and compare it to a recorded answer. The time is for the part
+// alone, without parsing.
+pub fn check(solution: &dyn Solution, part: u32, name: &str, expected: &str) -> (Status, Duration) {
+    let result = input::load(solution, &Source::Named(name.to_string())).and_then(|input| {
+        let (parsed, _) = timed(|| solution.parse(&input))?;
+        timed(|| solution.solve_parsed(part, parsed.as_ref(), &Config::for_input(name)))
+    });
+    match result {
+        Ok((answer, elapsed)) => {
+            let got = answer.to_string();
+            let status = if got == expected {
+                Status::Pass
+            } else {
+                Status::Fail { expected: expected.to_string(), got }
+            };
+            (status, elapsed)
+        }
+        Err(e) => (Status::Error(e), Duration::ZERO),
+    }
+}
+
+pub fn fmt_duration(d: Duration) -> String {
+    let micros = d.as_secs_f64() * 1_000_000.0;
+    if micros < 1000.0 {
+        format!("{:.0}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> Source {
+        Source::Named(name.to_string())
+    }
+
+    #[test]
+    fn solve_sample() {
+        let input = input(17, &named("sample")).unwrap();
+        let config = Config::for_input("sample");
+        assert_eq!(solve(17, 1, &input, &config), Ok(Answer::from("4,6,3,5,6,3,5,2,1,0")));
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert_eq!(input(26, &named("real")), Err(Error::UnknownDay(26)));
+        assert_eq!(solve(0, 1, "", &Config::default()), Err(Error::UnknownDay(0)));
+    }
+
+    #[test]
+    fn unknown_input() {
+        let err = input(3, &named("sample3")).unwrap_err();
+        assert_eq!(err, Error::UnknownInput { day: 3, name: "sample3".to_string() });
+        assert!(err.to_string().starts_with("unknown input \"sample3\" for day 3 (available: sample, sample2"));
+    }
+
+    #[test]
+    fn unknown_part() {
+        assert_eq!(solve(1, 3, "", &Config::default()), Err(Error::UnknownPart(3)));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = solve(1, 1, "3   4\n4   x\n", &Config::default()).unwrap_err();
+        assert!(matches!(err, Error::Parse(_)));
+        assert_eq!(err.to_string(), "day 1, line 2: expected a number, got \"x\": \"4   x\"");
+        let timing = time(day(1).unwrap(), "3 4", &Config::default());
+        assert!(matches!(timing.parse, Err(Error::Parse(_))));
+        assert!(timing.parts.is_none());
+    }
+
+    #[test]
+    fn params() {
+        let input = input(18, &named("sample")).unwrap();
+        let mut config = Config::for_input("sample");
+        assert_eq!(solve(18, 1, &input, &config), Ok(Answer::Int(22)));
+
+        // the same sample with fewer bytes fallen has a shorter path
+        config.overrides.push(("bytes".to_string(), "0".to_string()));
+        assert_eq!(solve(18, 1, &input, &config), Ok(Answer::Int(12)));
+
+        config.overrides.push(("width".to_string(), "7".to_string()));
+        let err = solve(18, 1, &input, &config).unwrap_err();
+        assert_eq!(err.to_string(), "unknown param \"width\" (expected size, bytes)");
+    }
+
+    #[test]
+    fn time_sample() {
+        let solution = day(1).unwrap();
+        let timing = time(solution, days::d01::inputs::SAMPLE, &Config::for_input("sample"));
+        assert_eq!(timing.day, 1);
+        assert!(timing.parse.is_ok());
+        let parts = timing.parts.as_ref().unwrap();
+        let answers: Vec<Answer> = parts.iter().map(|part| part.as_ref().unwrap().0.clone()).collect();
+        assert_eq!(answers, [Answer::Int(11), Answer::Int(31)]);
+        let parts_time: Duration = parts.iter().map(|part| part.as_ref().unwrap().1).sum();
+        assert_eq!(timing.total(), *timing.parse.as_ref().unwrap() + parts_time);
+    }
+
+    #[test]
+    fn time_catches_panics() {
+        let result = timed(|| -> Result<(), Error> { panic!("oops") });
+        assert_eq!(result, Err(Error::Panic("oops".to_string())));
+        let result = timed(|| -> Result<(), Error> { panic!("{} of {}", 1, 2) });
+        assert_eq!(result, Err(Error::Panic("1 of 2".to_string())));
+    }
+
+    #[test]
+    fn check_answers() {
+        let solution = day(3).unwrap();
+        assert_eq!(check(solution, 2, "sample2", "48").0, Status::Pass);
+        assert_eq!(
+            check(solution, 2, "sample2", "49").0,
+            Status::Fail { expected: "49".to_string(), got: "48".to_string() }
+        );
+        assert!(matches!(check(solution, 2, "sample9", "48").0, Status::Error(Error::UnknownInput { .. })));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(fmt_duration(Duration::from_micros(12)), "12µs");
+        assert_eq!(fmt_duration(Duration::from_micros(12_345)), "12.35ms");
+        assert_eq!(fmt_duration(Duration::from_millis(2_500)), "2.50s");
+    }
+
+    #[test]
+    fn registry_covers_every_day() {
+        let days: Vec<u32> = days::REGISTRY.iter().map(|d| d.day()).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u32>>());
+    }
+}
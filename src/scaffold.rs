@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::input;
+
+const TEMPLATE: &str = include_str!("days/template/mod.rs");
+
+// the crate's root, so `new-day` works from anywhere in the repo
+pub fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// The `src` and inputs dirs under `root`, so a new day's module and its real input land in the same
+// checkout. `$AOC_INPUTS` still moves the inputs, as it does for `run`.
+pub fn dirs(root: &Path) -> (PathBuf, PathBuf) {
+    (root.join("src"), input::dir_under(root))
+}
+
+// Add a day from the template: `days/dNN/mod.rs` with an empty sample under `src`, an empty real
+// input under `inputs`, and its line in the `days!` registry. Returns the files written.
+pub fn new_day(src: &Path, inputs: &Path, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Scaffold(format!("day {} is not between 1 and 25", day)));
+    }
+    let module = format!("d{:02}", day);
+    let dir = src.join("days").join(&module);
+    if dir.exists() {
+        return Err(Error::Scaffold(format!("{} already exists", dir.display())));
+    }
+    let days_rs = src.join("days.rs");
+    let registry = fs::read_to_string(&days_rs)
+        .map_err(|e| Error::Io { path: days_rs.clone(), message: e.to_string() })?;
+    let registry = register(&registry, day)?;
+
+    let mut written = Vec::new();
+    let mod_rs = dir.join("mod.rs");
+    let template = TEMPLATE.replace("const DAY: u32 = 0;", &format!("const DAY: u32 = {};", day));
+    write(&mod_rs, &template, &mut written)?;
+    write(&dir.join("sample"), "", &mut written)?;
+    let real = inputs.join(&module).join("real");
+    if !real.exists() {
+        write(&real, "", &mut written)?;
+    }
+    write(&days_rs, &registry, &mut written)?;
+    Ok(written)
+}
+
+// `days_rs` with `day` added to the `days!` registry, keeping the days in order
+fn register(days_rs: &str, day: u32) -> Result<String, Error> {
+    let not_found = || Error::Scaffold("can't find the `days! { ... }` registry in days.rs".to_string());
+    let start = days_rs.find("\ndays! {\n").ok_or_else(not_found)? + "\ndays! {\n".len();
+    let end = start + days_rs[start..].find("\n}").ok_or_else(not_found)? + 1;
+
+    let mut at = end;
+    let mut offset = start;
+    for line in days_rs[start..end].lines() {
+        let n = line.split("=>").next().and_then(|n| n.trim().parse::<u32>().ok());
+        if n == Some(day) {
+            return Err(Error::Scaffold(format!("day {} is already registered in days.rs", day)));
+        }
+        if n.is_some_and(|n| n > day) {
+            at = offset;
+            break;
+        }
+        offset += line.len() + 1;
+    }
+
+    let entry = format!("    {} => d{:02},\n", day, day);
+    Ok([&days_rs[..at], &entry, &days_rs[at..]].concat())
+}
+
+fn write(path: &Path, contents: &str, written: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let result = match path.parent() {
+        Some(dir) => fs::create_dir_all(dir).and_then(|()| fs::write(path, contents)),
+        None => fs::write(path, contents),
+    };
+    result.map_err(|e| Error::Scaffold(format!("can't write {}: {}", path.display(), e)))?;
+    written.push(path.to_path_buf());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS_RS: &str = "days! {\n    1 => d01,\n    3 => d03(params),\n}\n\npub fn get() {}\n";
+
+    #[test]
+    fn register_in_order() {
+        let days_rs = format!("use x;\n{}", DAYS_RS);
+        assert_eq!(register(&days_rs, 2).unwrap(), "use x;\ndays! {\n    1 => d01,\n    2 => d02,\n    3 => d03(params),\n}\n\npub fn get() {}\n");
+        assert!(register(&days_rs, 4).unwrap().contains("    3 => d03(params),\n    4 => d04,\n}\n"));
+        assert!(matches!(register(&days_rs, 3), Err(Error::Scaffold(_))));
+        assert!(matches!(register("fn main() {}", 3), Err(Error::Scaffold(_))));
+    }
+
+    #[test]
+    fn dirs_under_root() {
+        // both under the crate, wherever `new-day` is run from
+        let (src, inputs) = dirs(root());
+        assert_eq!(src, Path::new(env!("CARGO_MANIFEST_DIR")).join("src"));
+        if std::env::var_os("AOC_INPUTS").is_none() {
+            assert_eq!(inputs, Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+        }
+    }
+
+    #[test]
+    fn new_day_from_template() {
+        let root = crate::helpers::temp_dir("new_day");
+        let (src, inputs) = (root.join("src"), root.join("inputs"));
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("days.rs"), format!("\n{}", DAYS_RS)).unwrap();
+
+        let written = new_day(&src, &inputs, 7).unwrap();
+        assert_eq!(written, [
+            src.join("days/d07/mod.rs"),
+            src.join("days/d07/sample"),
+            inputs.join("d07/real"),
+            src.join("days.rs"),
+        ]);
+        let mod_rs = fs::read_to_string(src.join("days/d07/mod.rs")).unwrap();
+        assert!(mod_rs.contains("const DAY: u32 = 7;"));
+        assert!(fs::read_to_string(src.join("days.rs")).unwrap().contains("    7 => d07,\n}"));
+        assert!(matches!(new_day(&src, &inputs, 7), Err(Error::Scaffold(_))));
+        assert!(matches!(new_day(&src, &inputs, 26), Err(Error::Scaffold(_))));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
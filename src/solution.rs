@@ -0,0 +1,203 @@
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+// The answer to one part of a puzzle. Days return whatever type suits them and it gets
+// converted with `.into()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    // nothing to report, e.g. d24 part 2 checks itself with assertions
+    None,
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self { Answer::Int(n.into()) }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self { Answer::Int(n) }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self { Answer::Str(s) }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self { Answer::Str(s.to_string()) }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self { Answer::None }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+// Settings that come from the puzzle text rather than the input, like the size of the grid. The
+// defaults depend on which input is being solved, and any of them can be overridden by name.
+pub trait Params: Sized {
+    fn for_input(name: &str) -> Self;
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+// for days that don't take any
+impl Params for () {
+    fn for_input(_name: &str) -> Self {}
+
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(format!("unknown param {:?} (this day has no params)", key))
+    }
+}
+
+pub fn parse_param<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value {:?} for param {:?}", value, key))
+}
+
+// a param that's a size or count, which can't be 0 or less
+pub fn parse_positive<T: FromStr + PartialOrd + Default>(key: &str, value: &str) -> Result<T, String> {
+    let n = parse_param(key, value)?;
+    if n > T::default() { Ok(n) } else { Err(format!("param {:?} has to be more than 0, got {:?}", key, value)) }
+}
+
+// What a solution needs besides the input itself, to build its params.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    // the name of the input, which picks the default params
+    pub input: String,
+    // `key=value` overrides, applied in order
+    pub overrides: Vec<(String, String)>,
+}
+
+impl Config {
+    pub fn for_input(name: &str) -> Self {
+        Self { input: name.to_string(), overrides: Vec::new() }
+    }
+
+    pub fn params<P: Params>(&self) -> Result<P, Error> {
+        let mut params = P::for_input(&self.input);
+        for (key, value) in &self.overrides {
+            params.set(key, value).map_err(Error::Param)?;
+        }
+        Ok(params)
+    }
+}
+
+// A day's parsed input, whatever type its `parse` returns. Each part borrows it, so the input is
+// parsed once for both parts and parsing can be timed on its own.
+pub type Parsed = Box<dyn Any>;
+
+type ParseFn = fn(&str) -> Result<Parsed, Error>;
+type PartFn = fn(&dyn Any, &Config) -> Result<Answer, Error>;
+
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+
+    // names and contents of the inputs that ship with the day
+    fn inputs(&self) -> &'static [(&'static str, &'static str)];
+
+    fn parse(&self, input: &str) -> Result<Parsed, Error>;
+
+    // the parts take what `parse` returned for this day
+    fn part_1(&self, parsed: &dyn Any, config: &Config) -> Result<Answer, Error>;
+
+    fn part_2(&self, parsed: &dyn Any, config: &Config) -> Result<Answer, Error>;
+
+    fn input(&self, name: &str) -> Option<&'static str> {
+        self.inputs().iter().find(|(n, _)| *n == name).map(|(_, input)| *input)
+    }
+
+    fn solve_parsed(&self, part: u32, parsed: &dyn Any, config: &Config) -> Result<Answer, Error> {
+        match part {
+            1 => self.part_1(parsed, config),
+            2 => self.part_2(parsed, config),
+            _ => Err(Error::UnknownPart(part)),
+        }
+    }
+
+    // parse the input and solve one part of it
+    fn solve(&self, part: u32, input: &str, config: &Config) -> Result<Answer, Error> {
+        if !matches!(part, 1 | 2) { return Err(Error::UnknownPart(part)) }
+        let parsed = self.parse(input)?;
+        self.solve_parsed(part, parsed.as_ref(), config)
+    }
+}
+
+// A `Solution` built from a day module's free functions. The `days!` macro in `days.rs` creates
+// one of these for every module.
+pub struct Day {
+    day: u32,
+    inputs: &'static [(&'static str, &'static str)],
+    parse: ParseFn,
+    part_1: PartFn,
+    part_2: PartFn,
+}
+
+impl Day {
+    pub const fn new(
+        day: u32,
+        inputs: &'static [(&'static str, &'static str)],
+        parse: ParseFn,
+        part_1: PartFn,
+        part_2: PartFn,
+    ) -> Self {
+        Self { day, inputs, parse, part_1, part_2 }
+    }
+}
+
+impl Solution for Day {
+    fn day(&self) -> u32 { self.day }
+    fn inputs(&self) -> &'static [(&'static str, &'static str)] { self.inputs }
+    fn parse(&self, input: &str) -> Result<Parsed, Error> { (self.parse)(input) }
+    fn part_1(&self, parsed: &dyn Any, config: &Config) -> Result<Answer, Error> { (self.part_1)(parsed, config) }
+    fn part_2(&self, parsed: &dyn Any, config: &Config) -> Result<Answer, Error> { (self.part_2)(parsed, config) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_from() {
+        assert_eq!(Answer::from(42_i32), Answer::Int(42));
+        assert_eq!(Answer::from(1_i64 << 40), Answer::Int(1 << 40));
+        assert_eq!(Answer::from("6,1"), Answer::Str("6,1".to_string()));
+        assert_eq!(Answer::from(()), Answer::None);
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::Int(-3).to_string(), "-3");
+        assert_eq!(Answer::Str("co,de,ka,ta".to_string()).to_string(), "co,de,ka,ta");
+        assert_eq!(Answer::None.to_string(), "-");
+    }
+
+    #[test]
+    fn positive_params() {
+        assert_eq!(parse_positive::<i32>("width", "11"), Ok(11));
+        assert_eq!(parse_positive::<i32>("width", "0"), Err("param \"width\" has to be more than 0, got \"0\"".to_string()));
+        assert!(parse_positive::<i32>("width", "-3").is_err());
+        assert!(parse_positive::<i32>("width", "x").is_err());
+    }
+
+    #[test]
+    fn no_params() {
+        assert_eq!(Config::for_input("sample").params::<()>(), Ok(()));
+        let config = Config { input: "real".to_string(), overrides: vec![("size".to_string(), "7".to_string())] };
+        assert_eq!(
+            config.params::<()>(),
+            Err(Error::Param("unknown param \"size\" (this day has no params)".to_string()))
+        );
+    }
+}
@@ -0,0 +1,33 @@
+// The library from the outside, the way another crate would use it.
+
+use aoc_2024::days::{d17, d24};
+use aoc_2024::input::Source;
+use aoc_2024::{runner, Answer, Config, Grid};
+
+#[test]
+fn grid() {
+    let grid = Grid::from_str("ab\ncd");
+    assert_eq!(grid.get(1, 1), Some('d'));
+    assert_eq!(grid.find('c'), Some((0, 1)));
+}
+
+#[test]
+fn computer() {
+    let mut computer = d17::Computer::new(729, 0, 0, vec![0, 1, 5, 4, 3, 0]);
+    computer.run();
+    assert_eq!(computer.output(), "4,6,3,5,6,3,5,2,1,0");
+}
+
+#[test]
+fn gates() {
+    let mut gates = d24::parse(d24::inputs::SAMPLE).unwrap();
+    d24::run(&mut gates);
+    assert_eq!(d24::get_z(&gates), 4);
+    assert_eq!(d24::get_x(&gates), 7);
+}
+
+#[test]
+fn solve() {
+    let input = runner::input(3, &Source::Named("sample2".to_string())).unwrap();
+    assert_eq!(runner::solve(3, 2, &input, &Config::for_input("sample2")), Ok(Answer::Int(48)));
+}